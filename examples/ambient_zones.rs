@@ -0,0 +1,68 @@
+use bevy::{
+    color::palettes::css::{DARK_SLATE_BLUE, FOREST_GREEN, SADDLE_BROWN, SKY_BLUE},
+    prelude::*,
+};
+use bevy_light_2d::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .insert_resource(GlobalAmbientLight2d {
+            color: Color::Srgba(SKY_BLUE),
+            brightness: 1.0,
+        })
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((Camera2d, Light2d::default()));
+
+    commands.spawn(Sprite::from_color(
+        Color::Srgba(FOREST_GREEN),
+        Vec2::new(1200.0, 800.0),
+    ));
+
+    // A building, which is dark inside with a sharp edge at its walls.
+    commands.spawn((
+        Sprite::from_color(Color::Srgba(SADDLE_BROWN), Vec2::new(300.0, 200.0)),
+        AmbientZone2d {
+            shape: AmbientZone2dShape::Rectangle {
+                half_size: Vec2::new(150.0, 100.0),
+            },
+            brightness: 0.2,
+            ..default()
+        },
+        Transform::from_xyz(-300.0, 150.0, 1.0),
+    ));
+
+    // A cave, which darkens gradually from its mouth.
+    commands.spawn((
+        AmbientZone2d {
+            shape: AmbientZone2dShape::Polygon {
+                vertices: vec![
+                    Vec2::new(-150.0, -120.0),
+                    Vec2::new(150.0, -120.0),
+                    Vec2::new(200.0, 60.0),
+                    Vec2::new(0.0, 120.0),
+                    Vec2::new(-200.0, 60.0),
+                ],
+            },
+            color: Color::Srgba(DARK_SLATE_BLUE),
+            brightness: 0.1,
+            blend_distance: 120.0,
+        },
+        Transform::from_xyz(250.0, -120.0, 0.0),
+    ));
+
+    // A lantern lit clearing in the middle of the cave.
+    commands.spawn((
+        AmbientZone2d {
+            shape: AmbientZone2dShape::Circle { radius: 40.0 },
+            color: Color::srgb(1.0, 0.8, 0.5),
+            brightness: 0.8,
+            blend_distance: 40.0,
+        },
+        Transform::from_xyz(250.0, -140.0, 1.0),
+    ));
+}
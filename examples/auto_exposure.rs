@@ -0,0 +1,54 @@
+use bevy::{
+    color::palettes::css::{DIM_GRAY, FOREST_GREEN, ORANGE},
+    prelude::*,
+};
+use bevy_light_2d::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .insert_resource(GlobalAmbientLight2d {
+            color: Color::WHITE,
+            brightness: 2.0,
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, walk)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((Camera2d, Light2d::default(), AutoExposure2d::default()));
+
+    // Daylight on the left, and a dark cave lit by a single torch on the right.
+    commands.spawn((
+        Sprite::from_color(Color::Srgba(FOREST_GREEN), Vec2::new(1200.0, 600.0)),
+        Transform::from_xyz(-600.0, 0.0, 0.0),
+    ));
+    commands.spawn((
+        Sprite::from_color(Color::Srgba(DIM_GRAY), Vec2::new(1200.0, 600.0)),
+        AmbientZone2d {
+            shape: AmbientZone2dShape::Rectangle {
+                half_size: Vec2::new(600.0, 300.0),
+            },
+            brightness: 0.05,
+            ..default()
+        },
+        Transform::from_xyz(600.0, 0.0, 0.0),
+    ));
+    commands.spawn((
+        PointLight2d {
+            color: Color::Srgba(ORANGE),
+            intensity: 1.0,
+            radius: 200.0,
+            ..default()
+        },
+        LightFlicker2d::default(),
+        Transform::from_xyz(700.0, 0.0, 0.0),
+    ));
+}
+
+fn walk(mut cameras: Query<&mut Transform, With<Camera2d>>, time: Res<Time>) {
+    for mut transform in &mut cameras {
+        transform.translation.x = (time.elapsed_secs() * 0.25).sin() * 700.0;
+    }
+}
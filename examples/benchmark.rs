@@ -26,10 +26,11 @@ fn setup(mut commands: Commands) {
     commands.spawn((
         Camera2d,
         Light2d {
-            ambient_light: AmbientLight2d {
+            ambient_light: Some(AmbientLight2d {
                 brightness: 0.1,
                 ..default()
-            },
+            }),
+            ..default()
         },
     ));
 
@@ -0,0 +1,81 @@
+use bevy::{
+    color::palettes::css::{BEIGE, WHITE},
+    math::curve::UnevenSampleAutoCurve,
+    prelude::*,
+};
+use bevy_light_2d::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_grading)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                brightness: 0.3,
+                ..default()
+            }),
+            ..default()
+        },
+        teal_and_orange(),
+    ));
+
+    commands.spawn(Sprite::from_color(
+        Color::Srgba(BEIGE),
+        Vec2::new(800.0, 500.0),
+    ));
+
+    commands.spawn(PointLight2d {
+        color: Color::Srgba(WHITE),
+        intensity: 1.0,
+        radius: 300.0,
+        ..default()
+    });
+
+    commands.spawn((
+        Text::new("Space: toggle color grading"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12),
+            left: px(12),
+            ..default()
+        },
+    ));
+}
+
+/// Tints shadows blue and highlights orange.
+fn teal_and_orange() -> LightColorGrading2d {
+    let curve = |samples: [(f32, f32); 3]| {
+        UnevenSampleAutoCurve::new(samples).expect("the curve's samples should be valid")
+    };
+
+    LightColorGrading2d::Curves(LightColorCurves2d {
+        red: curve([(0.0, 0.0), (0.3, 0.2), (1.0, 1.0)]),
+        green: curve([(0.0, 0.0), (0.3, 0.25), (1.0, 0.9)]),
+        blue: curve([(0.0, 0.05), (0.3, 0.4), (1.0, 0.75)]),
+    })
+}
+
+fn toggle_grading(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    cameras: Query<(Entity, Has<LightColorGrading2d>), With<Light2d>>,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    for (entity, graded) in &cameras {
+        if graded {
+            commands.entity(entity).remove::<LightColorGrading2d>();
+        } else {
+            commands.entity(entity).insert(teal_and_orange());
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use bevy::{
+    color::palettes::css::{BLUE, CORNFLOWER_BLUE, RED, TAN},
+    prelude::*,
+};
+use bevy_light_2d::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (change_composite, update_text).chain())
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                brightness: 0.3,
+                ..default()
+            }),
+            ..default()
+        },
+        LightComposite2d::default(),
+    ));
+
+    for (i, color) in [TAN, CORNFLOWER_BLUE].into_iter().enumerate() {
+        commands.spawn((
+            Sprite::from_color(Color::Srgba(color), Vec2::new(600.0, 300.0)),
+            Transform::from_xyz(0.0, 150.0 - 300.0 * i as f32, 0.0),
+        ));
+    }
+
+    // A bright light, which shows how light beyond `1.0` is handled.
+    commands.spawn((
+        PointLight2d {
+            color: Color::Srgba(RED),
+            intensity: 4.0,
+            radius: 250.0,
+            ..default()
+        },
+        Transform::from_xyz(-150.0, 0.0, 0.0),
+    ));
+    commands.spawn((
+        PointLight2d {
+            color: Color::Srgba(BLUE),
+            intensity: 1.0,
+            radius: 250.0,
+            ..default()
+        },
+        Transform::from_xyz(150.0, 0.0, 0.0),
+    ));
+
+    commands.spawn((
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12),
+            left: px(12),
+            ..default()
+        },
+    ));
+}
+
+fn change_composite(keys: Res<ButtonInput<KeyCode>>, mut composites: Query<&mut LightComposite2d>) {
+    for mut composite in &mut composites {
+        if keys.just_pressed(KeyCode::Space) {
+            composite.mode = match composite.mode {
+                LightCompositeMode::Multiply => LightCompositeMode::SoftLight,
+                LightCompositeMode::SoftLight => LightCompositeMode::Overlay,
+                LightCompositeMode::Overlay => LightCompositeMode::MultiplyAdditive,
+                LightCompositeMode::MultiplyAdditive => LightCompositeMode::Multiply,
+            };
+        }
+        if keys.just_pressed(KeyCode::KeyG) {
+            composite.space = match composite.space {
+                LightCompositeSpace::Linear => LightCompositeSpace::Gamma,
+                LightCompositeSpace::Gamma => LightCompositeSpace::Linear,
+            };
+        }
+        if keys.just_pressed(KeyCode::ArrowUp) {
+            composite.exposure *= 1.25;
+        }
+        if keys.just_pressed(KeyCode::ArrowDown) {
+            composite.exposure /= 1.25;
+        }
+    }
+}
+
+fn update_text(composites: Query<&LightComposite2d>, mut texts: Query<&mut Text>) {
+    let Ok(composite) = composites.single() else {
+        return;
+    };
+
+    for mut text in &mut texts {
+        text.0 = format!(
+            "Mode (Space): {:?}\nSpace (G): {:?}\nExposure (Up/Down): {:.2}",
+            composite.mode, composite.space, composite.exposure
+        );
+    }
+}
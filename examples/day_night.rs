@@ -0,0 +1,121 @@
+use bevy::{
+    color::palettes::css::{DARK_GREEN, SADDLE_BROWN, YELLOW},
+    prelude::*,
+};
+use bevy_light_2d::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .insert_resource(DayNightCycle2d {
+            day_length: 30.0,
+            ..default()
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, (control_time, move_sun))
+        .add_observer(light_lamp)
+        .add_observer(put_out_lamp)
+        .run();
+}
+
+#[derive(Component)]
+struct Lamp;
+
+#[derive(Component)]
+struct Sun;
+
+fn setup(mut commands: Commands) {
+    commands.spawn((Camera2d, Light2d::default()));
+
+    commands.spawn((
+        Sprite::from_color(Color::Srgba(DARK_GREEN), Vec2::new(1200.0, 200.0)),
+        Transform::from_xyz(0.0, -250.0, 0.0),
+    ));
+    commands.spawn((
+        Sprite::from_color(Color::Srgba(SADDLE_BROWN), Vec2::new(200.0, 150.0)),
+        Transform::from_xyz(0.0, -75.0, 0.0),
+    ));
+
+    // A street lamp, which is lit at sunset and put out at sunrise.
+    commands.spawn((
+        PointLight2d {
+            color: Color::Srgba(YELLOW),
+            intensity: 0.0,
+            radius: 250.0,
+            ..default()
+        },
+        Transform::from_xyz(200.0, -50.0, 0.0),
+        Lamp,
+    ));
+
+    commands.spawn((
+        Sprite::from_color(Color::Srgba(YELLOW), Vec2::splat(40.0)),
+        Unlit2d::default(),
+        Sun,
+    ));
+
+    commands.spawn((
+        Text::new("Space: pause, Up/Down: speed, 1-4: jump to morning/noon/evening/midnight"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12),
+            left: px(12),
+            ..default()
+        },
+    ));
+}
+
+fn control_time(keys: Res<ButtonInput<KeyCode>>, mut cycle: ResMut<DayNightCycle2d>) {
+    if keys.just_pressed(KeyCode::Space) {
+        if cycle.paused {
+            cycle.resume();
+        } else {
+            cycle.pause();
+        }
+    }
+    if keys.just_pressed(KeyCode::ArrowUp) {
+        cycle.speed *= 2.0;
+    }
+    if keys.just_pressed(KeyCode::ArrowDown) {
+        cycle.speed *= 0.5;
+    }
+
+    for (key, hour) in [
+        (KeyCode::Digit1, 6.0),
+        (KeyCode::Digit2, 12.0),
+        (KeyCode::Digit3, 18.0),
+        (KeyCode::Digit4, 0.0),
+    ] {
+        if keys.just_pressed(key) {
+            cycle.jump_to(hour);
+        }
+    }
+}
+
+fn move_sun(
+    cycle: Res<DayNightCycle2d>,
+    mut suns: Query<(&mut Transform, &mut Visibility), With<Sun>>,
+) {
+    for (mut transform, mut visibility) in &mut suns {
+        match cycle.sun() {
+            Some(sun) => {
+                // The sun sits opposite the direction its light travels.
+                transform.translation = (-*sun.direction * 300.0).extend(0.0);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+fn light_lamp(_: On<Sunset2d>, mut lamps: Query<&mut PointLight2d, With<Lamp>>) {
+    for mut lamp in &mut lamps {
+        lamp.intensity = 2.0;
+    }
+}
+
+fn put_out_lamp(_: On<Sunrise2d>, mut lamps: Query<&mut PointLight2d, With<Lamp>>) {
+    for mut lamp in &mut lamps {
+        lamp.intensity = 0.0;
+    }
+}
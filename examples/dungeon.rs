@@ -44,10 +44,11 @@ fn setup_camera(mut commands: Commands) {
         Camera2d,
         Projection::Orthographic(projection),
         Light2d {
-            ambient_light: AmbientLight2d {
+            ambient_light: Some(AmbientLight2d {
                 brightness: 0.1,
                 ..default()
-            },
+            }),
+            ..default()
         },
     ));
 }
@@ -79,6 +80,15 @@ fn spawn_candles(mut commands: Commands, spritesheet: Res<CandleSpritesheet>) {
                 color: Color::Srgba(YELLOW),
                 intensity: 2.0,
                 falloff: 4.0,
+                halo: Some(LightHalo2d {
+                    intensity: 0.5,
+                    size: 8.0,
+                }),
+                ..default()
+            },
+            LightFlicker2d {
+                amplitude: 0.15,
+                radius_amplitude: 0.05,
                 ..default()
             },
         ))
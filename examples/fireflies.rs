@@ -0,0 +1,59 @@
+use bevy::{color::palettes::css::GREEN_YELLOW, prelude::*};
+use bevy_light_2d::prelude::*;
+
+const FIREFLY_COUNT: usize = 2000;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_fireflies)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                brightness: 0.05,
+                ..default()
+            }),
+            ..default()
+        },
+    ));
+
+    commands.spawn(Sprite {
+        custom_size: Some(Vec2::new(1200.0, 800.0)),
+        color: Color::WHITE,
+        ..default()
+    });
+
+    commands.spawn(LightEmitterBatch2d {
+        emitters: (0..FIREFLY_COUNT)
+            .map(|_| LightEmitter2d {
+                color: Color::Srgba(GREEN_YELLOW),
+                radius: 12.0,
+                intensity: 1.5,
+                ..default()
+            })
+            .collect(),
+        falloff: 2.0,
+    });
+}
+
+fn move_fireflies(time: Res<Time>, mut batches: Query<&mut LightEmitterBatch2d>) {
+    let t = time.elapsed_secs();
+
+    for mut batch in &mut batches {
+        for (i, emitter) in batch.emitters.iter_mut().enumerate() {
+            // Give each firefly its own slow, wandering path.
+            let i = i as f32;
+            emitter.offset = Vec2::new(
+                (i * 12.9898).sin() * 550.0 + (t * 0.7 + i).sin() * 30.0,
+                (i * 78.233).sin() * 350.0 + (t * 0.9 + i * 1.3).cos() * 30.0,
+            );
+            emitter.intensity = 1.0 + (t * 3.0 + i).sin() * 0.5;
+        }
+    }
+}
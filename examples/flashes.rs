@@ -0,0 +1,60 @@
+use bevy::{color::palettes::css::ORANGE, prelude::*};
+use bevy_light_2d::prelude::*;
+
+const FLASHES_PER_SECOND: f32 = 60.0;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .insert_resource(FlashTimer(Timer::from_seconds(
+            1.0 / FLASHES_PER_SECOND,
+            TimerMode::Repeating,
+        )))
+        .add_systems(Startup, setup)
+        .add_systems(Update, spawn_flashes)
+        .run();
+}
+
+#[derive(Resource, Deref, DerefMut)]
+struct FlashTimer(Timer);
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                brightness: 0.05,
+                ..default()
+            }),
+            ..default()
+        },
+    ));
+
+    commands.spawn(Sprite {
+        custom_size: Some(Vec2::new(1200.0, 800.0)),
+        color: Color::WHITE,
+        ..default()
+    });
+}
+
+fn spawn_flashes(mut commands: Commands, time: Res<Time>, mut timer: ResMut<FlashTimer>) {
+    timer.tick(time.delta());
+
+    for i in 0..timer.times_finished_this_tick() {
+        // Scatter the flashes around the screen without pulling in a random number generator.
+        let seed = time.elapsed_secs() * 7.31 + i as f32 * 1.37;
+        let position = Vec2::new((seed * 3.17).sin() * 500.0, (seed * 2.23).cos() * 300.0);
+
+        commands.spawn_light_flash(
+            position,
+            TransientLight2d {
+                color: Color::Srgba(ORANGE),
+                intensity: 3.0,
+                radius: 80.0,
+                duration: 0.25,
+                radius_curve: EasingCurve::new(0.5, 1.0, EaseFunction::QuadraticOut),
+                ..default()
+            },
+        );
+    }
+}
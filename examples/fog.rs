@@ -0,0 +1,103 @@
+use bevy::{
+    color::palettes::css::{LIGHT_YELLOW, WHEAT},
+    prelude::*,
+};
+use bevy_light_2d::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, sweep_flashlight)
+        .run();
+}
+
+#[derive(Component)]
+struct Flashlight;
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                brightness: 0.05,
+                ..default()
+            }),
+            ..default()
+        },
+        // A light haze everywhere, so the flashlight's beam is visible.
+        Fog2d {
+            density: 0.15,
+            ..default()
+        },
+    ));
+
+    // Thick dust in the room lit by the window.
+    commands.spawn((
+        FogVolume2d {
+            half_size: Vec2::new(300.0, 200.0),
+            density: 0.6,
+            falloff: 80.0,
+        },
+        Transform::from_xyz(0.0, 50.0, 0.0),
+    ));
+
+    // Sunlight shining through the bars of a window, casting shafts into the dust.
+    commands.spawn((
+        PointLight2d {
+            color: Color::Srgba(LIGHT_YELLOW),
+            intensity: 3.0,
+            radius: 700.0,
+            falloff: 1.0,
+            cast_shadows: true,
+            ..default()
+        },
+        Transform::from_xyz(0.0, 350.0, 0.0),
+    ));
+
+    for x in -3..=3 {
+        commands.spawn((
+            LightOccluder2d {
+                shape: LightOccluder2dShape::Rectangle {
+                    half_size: Vec2::new(8.0, 20.0),
+                },
+                ..default()
+            },
+            Transform::from_xyz(x as f32 * 40.0, 270.0, 0.0),
+        ));
+    }
+
+    for x in [-1.0, 1.0] {
+        commands.spawn((
+            LightOccluder2d {
+                shape: LightOccluder2dShape::Rectangle {
+                    half_size: Vec2::new(400.0, 20.0),
+                },
+                ..default()
+            },
+            Transform::from_xyz(x * 540.0, 270.0, 0.0),
+        ));
+    }
+
+    commands.spawn((
+        SpotLight2d {
+            color: Color::Srgba(WHEAT),
+            intensity: 4.0,
+            radius: 600.0,
+            falloff: 2.0,
+            inner_angle: 170.0,
+            outer_angle: 160.0,
+            source_width: 4.0,
+            cast_shadows: true,
+            ..default()
+        },
+        Transform::from_xyz(0.0, -300.0, 0.0),
+        Flashlight,
+    ));
+}
+
+fn sweep_flashlight(mut flashlights: Query<&mut SpotLight2d, With<Flashlight>>, time: Res<Time>) {
+    for mut flashlight in &mut flashlights {
+        flashlight.direction = 90.0 + time.elapsed_secs().sin() * 40.0;
+    }
+}
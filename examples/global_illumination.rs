@@ -0,0 +1,85 @@
+use bevy::{
+    color::palettes::css::{CRIMSON, LIGHT_YELLOW, LIME},
+    prelude::*,
+};
+use bevy_light_2d::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_global_illumination)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                brightness: 0.02,
+                ..default()
+            }),
+            ..default()
+        },
+        GlobalIllumination2d::default(),
+    ));
+
+    commands.spawn(Text::new("Press space to toggle global illumination"));
+
+    commands.spawn(Sprite::from_color(Color::WHITE, Vec2::new(1200.0, 800.0)));
+
+    // A red wall next to the torch, which casts red light back into the room.
+    commands.spawn((
+        Sprite::from_color(CRIMSON, Vec2::new(40.0, 400.0)),
+        LightOccluder2d {
+            shape: LightOccluder2dShape::Rectangle {
+                half_size: Vec2::new(20.0, 200.0),
+            },
+            ..default()
+        },
+        Transform::from_xyz(-200.0, 0.0, 1.0),
+    ));
+
+    commands.spawn((
+        PointLight2d {
+            color: Color::Srgba(LIGHT_YELLOW),
+            intensity: 3.0,
+            radius: 250.0,
+            falloff: 2.0,
+            cast_shadows: true,
+            ..default()
+        },
+        Transform::from_xyz(-130.0, 0.0, 0.0),
+    ));
+
+    // A glowing crystal, which lights the room around it without a light of its own.
+    commands.spawn((
+        Sprite::from_color(LIME, Vec2::splat(24.0)),
+        Emissive2d {
+            intensity: 4.0,
+            ..default()
+        },
+        Transform::from_xyz(250.0, -150.0, 1.0),
+    ));
+}
+
+fn toggle_global_illumination(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    cameras: Query<(Entity, Has<GlobalIllumination2d>), With<Light2d>>,
+) {
+    if !keyboard.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    for (entity, enabled) in &cameras {
+        if enabled {
+            commands.entity(entity).remove::<GlobalIllumination2d>();
+        } else {
+            commands
+                .entity(entity)
+                .insert(GlobalIllumination2d::default());
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use bevy::{
+    camera::Hdr,
+    color::palettes::css::{DARK_SLATE_GRAY, ORANGE_RED, TURQUOISE},
+    core_pipeline::tonemapping::Tonemapping,
+    post_process::bloom::Bloom,
+    prelude::*,
+};
+use bevy_light_2d::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // Light brighter than 1.0 is kept on an HDR camera, so it blooms and is tonemapped.
+    commands.spawn((
+        Camera2d,
+        Hdr,
+        Tonemapping::AcesFitted,
+        Bloom::NATURAL,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                brightness: 0.2,
+                ..default()
+            }),
+            ..default()
+        },
+        LightComposite2d {
+            mode: LightCompositeMode::MultiplyAdditive,
+            ..default()
+        },
+    ));
+
+    commands.spawn(Sprite::from_color(
+        Color::Srgba(DARK_SLATE_GRAY),
+        Vec2::new(800.0, 500.0),
+    ));
+
+    for (x, color) in [(-200.0, ORANGE_RED), (200.0, TURQUOISE)] {
+        commands.spawn((
+            PointLight2d {
+                color: Color::Srgba(color),
+                intensity: 4.0,
+                radius: 200.0,
+                ..default()
+            },
+            LightPulse2d {
+                amplitude: 0.75,
+                frequency: 0.25,
+                ..default()
+            },
+            Transform::from_xyz(x, 0.0, 0.0),
+        ));
+    }
+}
@@ -31,10 +31,11 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn((
         Camera2d,
         Light2d {
-            ambient_light: AmbientLight2d {
+            ambient_light: Some(AmbientLight2d {
                 brightness: 0.02,
                 ..default()
-            },
+            }),
+            ..default()
         },
     ));
 
@@ -0,0 +1,61 @@
+use bevy::{color::palettes::css::LIGHT_CYAN, prelude::*};
+use bevy_light_2d::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, drift_asteroid)
+        .run();
+}
+
+#[derive(Component)]
+struct Asteroid;
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                brightness: 0.0,
+                ..default()
+            }),
+            ..default()
+        },
+    ));
+
+    // A distant star, which is only visible through its halo and lens flare.
+    commands.spawn((
+        PointLight2d {
+            color: Color::Srgba(LIGHT_CYAN),
+            intensity: 2.0,
+            radius: 300.0,
+            halo: Some(LightHalo2d {
+                intensity: 2.0,
+                size: 40.0,
+            }),
+            ..default()
+        },
+        LensFlare2d::default(),
+        Transform::from_xyz(-250.0, 150.0, 0.0),
+    ));
+
+    // An asteroid that passes in front of the star, hiding the flare.
+    commands.spawn((
+        Sprite::from_color(Color::srgb(0.3, 0.25, 0.2), Vec2::splat(60.0)),
+        LightOccluder2d {
+            shape: LightOccluder2dShape::Rectangle {
+                half_size: Vec2::splat(30.0),
+            },
+            ..default()
+        },
+        Transform::from_xyz(-250.0, 150.0, 1.0),
+        Asteroid,
+    ));
+}
+
+fn drift_asteroid(mut asteroids: Query<&mut Transform, With<Asteroid>>, time: Res<Time>) {
+    for mut transform in &mut asteroids {
+        transform.translation.x = -250.0 + (time.elapsed_secs() * 0.5).sin() * 200.0;
+    }
+}
@@ -0,0 +1,149 @@
+use bevy::{
+    color::palettes::css::{LIGHT_CYAN, LIME, ORANGE_RED},
+    prelude::*,
+};
+use bevy_light_2d::prelude::*;
+
+const LASER_POSITION: Vec2 = Vec2::new(-400.0, -200.0);
+const LASER_RANGE: f32 = 1500.0;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (aim_laser, check_target).chain())
+        .run();
+}
+
+#[derive(Component)]
+struct Laser;
+
+#[derive(Component)]
+struct Target;
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                brightness: 0.05,
+                ..default()
+            }),
+            ..default()
+        },
+    ));
+
+    commands.spawn(Text::new("Move the mouse to aim the laser"));
+
+    commands.spawn(Sprite::from_color(Color::WHITE, Vec2::new(1200.0, 800.0)));
+
+    // A narrow beam, which bounces off both mirrors.
+    commands.spawn((
+        SpotLight2d {
+            color: Color::Srgba(ORANGE_RED),
+            intensity: 4.0,
+            radius: LASER_RANGE,
+            falloff: 0.5,
+            inner_angle: 178.0,
+            outer_angle: 176.0,
+            source_width: 0.0,
+            cast_shadows: true,
+            max_reflections: 2,
+            ..default()
+        },
+        Transform::from_translation(LASER_POSITION.extend(0.0)),
+        Laser,
+    ));
+
+    // Mirrors, the second of which tints the beam it reflects.
+    for (position, half_size, tint) in [
+        (
+            Vec2::new(300.0, -200.0),
+            Vec2::new(10.0, 120.0),
+            Color::WHITE,
+        ),
+        (
+            Vec2::new(300.0, 250.0),
+            Vec2::new(120.0, 10.0),
+            Color::Srgba(LIGHT_CYAN),
+        ),
+    ] {
+        commands.spawn((
+            Sprite::from_color(Color::srgb(0.7, 0.8, 0.9), half_size * 2.0),
+            LightOccluder2d {
+                shape: LightOccluder2dShape::Rectangle { half_size },
+                reflectivity: 0.9,
+                reflection_tint: tint,
+                ..default()
+            },
+            Transform::from_translation(position.extend(1.0)),
+        ));
+    }
+
+    // The target, which lights up when the beam reaches it.
+    commands.spawn((
+        Sprite::from_color(Color::srgb(0.2, 0.2, 0.2), Vec2::splat(40.0)),
+        LightOccluder2d {
+            shape: LightOccluder2dShape::Rectangle {
+                half_size: Vec2::splat(20.0),
+            },
+            ..default()
+        },
+        Transform::from_xyz(-300.0, 250.0, 1.0),
+        Target,
+    ));
+}
+
+fn aim_laser(
+    mut lasers: Query<&mut SpotLight2d, With<Laser>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.single() else {
+        return;
+    };
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+
+    for mut laser in &mut lasers {
+        laser.direction = (cursor - LASER_POSITION).to_angle().to_degrees();
+    }
+}
+
+fn check_target(
+    lasers: Query<&SpotLight2d, With<Laser>>,
+    mut targets: Query<(Entity, &mut Sprite), With<Target>>,
+    light_beams: LightBeams2d,
+) {
+    let Ok(laser) = lasers.single() else {
+        return;
+    };
+    let Ok((target, mut sprite)) = targets.single_mut() else {
+        return;
+    };
+
+    let direction = Dir2::from_xy(
+        laser.direction.to_radians().cos(),
+        laser.direction.to_radians().sin(),
+    )
+    .unwrap_or(Dir2::X);
+    let beam = light_beams.trace(
+        LASER_POSITION,
+        direction,
+        laser.radius,
+        laser.max_reflections,
+    );
+
+    sprite.color = if beam.blocked_by == Some(target) {
+        Color::Srgba(LIME)
+    } else {
+        Color::srgb(0.2, 0.2, 0.2)
+    };
+}
@@ -0,0 +1,88 @@
+use bevy::{
+    color::palettes::css::{DEEP_PINK, DEEP_SKY_BLUE, GOLD, ORANGE_RED},
+    prelude::*,
+};
+use bevy_light_2d::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, flicker_sign)
+        .run();
+}
+
+#[derive(Component)]
+struct FaultySign;
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                brightness: 0.05,
+                ..default()
+            }),
+            emissive_blur: 24.0,
+            ..default()
+        },
+    ));
+
+    commands.spawn(Sprite {
+        custom_size: Some(Vec2::new(1200.0, 800.0)),
+        color: Color::WHITE,
+        ..default()
+    });
+
+    // Neon tubes glow in their own shape, without needing a light for each one.
+    for (color, position, size) in [
+        (DEEP_PINK, Vec2::new(0.0, 120.0), Vec2::new(400.0, 12.0)),
+        (DEEP_PINK, Vec2::new(0.0, -120.0), Vec2::new(400.0, 12.0)),
+        (
+            DEEP_SKY_BLUE,
+            Vec2::new(-200.0, 0.0),
+            Vec2::new(12.0, 252.0),
+        ),
+        (DEEP_SKY_BLUE, Vec2::new(200.0, 0.0), Vec2::new(12.0, 252.0)),
+    ] {
+        commands.spawn((
+            Sprite::from_color(color, size),
+            Emissive2d {
+                intensity: 3.0,
+                ..default()
+            },
+            Transform::from_translation(position.extend(1.0)),
+        ));
+    }
+
+    commands.spawn((
+        Sprite::from_color(GOLD, Vec2::new(200.0, 40.0)),
+        Emissive2d {
+            intensity: 2.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 1.0),
+        FaultySign,
+    ));
+
+    // A glowing wall lights the floor around it, but still blocks light passing through.
+    commands.spawn((
+        LightOccluder2d {
+            shape: LightOccluder2dShape::Rectangle {
+                half_size: Vec2::new(150.0, 10.0),
+            },
+            emission: Some((Color::linear_rgb(2.0, 0.4, 0.05), 80.0)),
+            ..default()
+        },
+        Sprite::from_color(ORANGE_RED, Vec2::new(300.0, 20.0)),
+        Transform::from_xyz(0.0, -250.0, 1.0),
+    ));
+}
+
+fn flicker_sign(time: Res<Time>, mut signs: Query<&mut Emissive2d, With<FaultySign>>) {
+    let on = (time.elapsed_secs() * 7.0).sin() > -0.8;
+
+    for mut emissive in &mut signs {
+        emissive.intensity = if on { 2.0 } else { 0.2 };
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    color::palettes::css::{ORANGE, SLATE_GRAY},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_light_2d::prelude::*;
+
+const TILE_SIZE: u32 = 64;
+const STUDS_PER_TILE: u32 = 4;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_light)
+        .run();
+}
+
+#[derive(Component)]
+struct Lantern;
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                brightness: 0.1,
+                ..default()
+            }),
+            ..default()
+        },
+    ));
+
+    let normal_map = images.add(studded_normal_map());
+
+    // A shiny studded metal floor, lit by a lantern hovering above it.
+    for x in -3..3 {
+        for y in -2..2 {
+            commands.spawn((
+                Sprite::from_color(SLATE_GRAY, Vec2::splat(TILE_SIZE as f32 * 2.0)),
+                NormalMap2d(normal_map.clone()),
+                Specular2d {
+                    intensity: 0.6,
+                    gloss: 24.0,
+                    ..default()
+                },
+                Transform::from_xyz(
+                    (x as f32 + 0.5) * TILE_SIZE as f32 * 2.0,
+                    (y as f32 + 0.5) * TILE_SIZE as f32 * 2.0,
+                    0.0,
+                ),
+            ));
+        }
+    }
+
+    commands.spawn((
+        PointLight2d {
+            color: Color::Srgba(ORANGE),
+            intensity: 4.0,
+            radius: 400.0,
+            falloff: 2.0,
+            height: 60.0,
+            ..default()
+        },
+        Lantern,
+    ));
+}
+
+fn move_light(mut lanterns: Query<&mut Transform, With<Lantern>>, time: Res<Time>) {
+    let t = time.elapsed_secs();
+    for mut transform in &mut lanterns {
+        transform.translation.x = (t * 0.7).sin() * 300.0;
+        transform.translation.y = (t * 1.1).cos() * 150.0;
+    }
+}
+
+/// Builds a tangent space normal map of rounded studs.
+fn studded_normal_map() -> Image {
+    let stud_size = TILE_SIZE / STUDS_PER_TILE;
+    let mut data = Vec::with_capacity((TILE_SIZE * TILE_SIZE * 4) as usize);
+
+    for y in 0..TILE_SIZE {
+        for x in 0..TILE_SIZE {
+            // Position within the stud, from -1.0 to 1.0. Image rows go downwards, while
+            // normal maps point +Y upwards.
+            let local = Vec2::new(
+                ((x % stud_size) as f32 + 0.5) / stud_size as f32 * 2.0 - 1.0,
+                -(((y % stud_size) as f32 + 0.5) / stud_size as f32 * 2.0 - 1.0),
+            );
+
+            // Each stud is a low dome, surrounded by flat ground.
+            let slope = if local.length() < 0.8 {
+                local * 0.8
+            } else {
+                Vec2::ZERO
+            };
+            let normal = Vec3::new(slope.x, slope.y, 1.0).normalize();
+
+            let encoded = (normal * 0.5 + 0.5) * 255.0;
+            data.extend_from_slice(&[encoded.x as u8, encoded.y as u8, encoded.z as u8, 255]);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: TILE_SIZE,
+            height: TILE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        // Normal maps hold directions rather than colors, so they aren't gamma corrected.
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
@@ -29,7 +29,13 @@ struct RedLight;
 struct GreenLight;
 
 fn setup(mut commands: Commands) {
-    commands.spawn((Camera2d, Light2d::default()));
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_occlusion: Some(AmbientOcclusion2d::default()),
+            ..default()
+        },
+    ));
 
     commands.spawn((
         PointLight2d {
@@ -38,6 +44,7 @@ fn setup(mut commands: Commands) {
             falloff: 10.0,
             cast_shadows: true,
             color: Color::Srgba(YELLOW),
+            ..default()
         },
         Transform::from_translation(Vec3::new(0.0, 200.0, 0.0)),
         YellowLight,
@@ -50,6 +57,7 @@ fn setup(mut commands: Commands) {
             falloff: 10.0,
             cast_shadows: true,
             color: Color::Srgba(BLUE),
+            ..default()
         },
         Transform::from_translation(Vec3::new(0.0, 200.0, 0.0)),
         BlueLight,
@@ -66,6 +74,7 @@ fn setup(mut commands: Commands) {
             source_width: 10.0,
             cast_shadows: true,
             color: Color::Srgba(RED),
+            ..default()
         },
         Transform::from_translation(Vec3::new(0.0, -200.0, 0.0)),
         RedLight,
@@ -82,6 +91,7 @@ fn setup(mut commands: Commands) {
             source_width: 10.0,
             cast_shadows: true,
             color: Color::Srgba(GREEN),
+            ..default()
         },
         Transform::from_translation(Vec3::new(0.0, -200.0, 0.0)),
         GreenLight,
@@ -92,6 +102,7 @@ fn setup(mut commands: Commands) {
             shape: LightOccluder2dShape::Rectangle {
                 half_size: Vec2::splat(25.0),
             },
+            ..default()
         },
         Transform::from_xyz(-400.0, 0., 0.0),
     ));
@@ -101,6 +112,7 @@ fn setup(mut commands: Commands) {
             shape: LightOccluder2dShape::Rectangle {
                 half_size: Vec2::splat(25.0),
             },
+            ..default()
         },
         Transform::from_xyz(-200.0, 0.0, 0.0),
     ));
@@ -110,6 +122,7 @@ fn setup(mut commands: Commands) {
             shape: LightOccluder2dShape::Rectangle {
                 half_size: Vec2::splat(25.0),
             },
+            ..default()
         },
         Transform::from_xyz(0.0, 0.0, 0.0),
     ));
@@ -119,6 +132,7 @@ fn setup(mut commands: Commands) {
             shape: LightOccluder2dShape::Rectangle {
                 half_size: Vec2::splat(25.0),
             },
+            ..default()
         },
         Transform::from_xyz(200.0, 0.0, 0.0),
     ));
@@ -128,6 +142,7 @@ fn setup(mut commands: Commands) {
             shape: LightOccluder2dShape::Rectangle {
                 half_size: Vec2::splat(25.0),
             },
+            ..default()
         },
         Transform::from_xyz(400.0, 0.0, 0.0),
     ));
@@ -0,0 +1,61 @@
+use bevy::{
+    color::palettes::css::{DARK_SLATE_GRAY, LIGHT_SEA_GREEN, ORANGE, YELLOW},
+    prelude::*,
+};
+use bevy_light_2d::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, Light2dPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, dive)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // The water gets darker and bluer the deeper the camera dives.
+    commands.spawn((
+        Camera2d,
+        Light2d {
+            ambient_light: Some(AmbientLight2d {
+                source: AmbientLight2dSource::Gradient {
+                    sky: Color::Srgba(LIGHT_SEA_GREEN),
+                    ground: Color::srgb(0.0, 0.02, 0.08),
+                    mapping: AmbientLight2dMapping::World(Rect::new(-1000.0, -1200.0, 1000.0, 0.0)),
+                },
+                ..default()
+            }),
+            ..default()
+        },
+    ));
+
+    for i in 0..8 {
+        let depth = -150.0 * i as f32;
+        commands.spawn((
+            Sprite::from_color(Color::Srgba(ORANGE), Vec2::new(40.0, 20.0)),
+            Transform::from_xyz(if i % 2 == 0 { -120.0 } else { 120.0 }, depth, 0.0),
+        ));
+    }
+
+    commands.spawn((
+        Sprite::from_color(Color::Srgba(DARK_SLATE_GRAY), Vec2::new(2000.0, 100.0)),
+        Transform::from_xyz(0.0, -1250.0, 0.0),
+    ));
+
+    // An anglerfish's lure, only noticeable in the depths.
+    commands.spawn((
+        PointLight2d {
+            color: Color::Srgba(YELLOW),
+            intensity: 2.0,
+            radius: 150.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, -1100.0, 0.0),
+    ));
+}
+
+fn dive(mut cameras: Query<&mut Transform, With<Camera2d>>, time: Res<Time>) {
+    for mut transform in &mut cameras {
+        transform.translation.y = -550.0 + (time.elapsed_secs() * 0.3).cos() * 550.0;
+    }
+}
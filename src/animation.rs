@@ -0,0 +1,158 @@
+//! A module which exposes light properties to `bevy_animation`.
+//!
+//! Numeric fields, such as a light's intensity, radius, or a spot light's angles, can be animated
+//! directly with [`animated_field`](bevy::animation::animated_field):
+//!
+//! ```no_run
+//! # use bevy::{animation::{AnimationTargetId, animated_field}, prelude::*};
+//! # use bevy_light_2d::prelude::*;
+//! # let mut clip = AnimationClip::default();
+//! # let target = AnimationTargetId::from_name(&Name::new("torch"));
+//! clip.add_curve_to_target(
+//!     target,
+//!     AnimatableCurve::new(
+//!         animated_field!(PointLight2d::intensity),
+//!         EasingCurve::new(0.0, 4.0, EaseFunction::CubicOut),
+//!     ),
+//! );
+//! ```
+//!
+//! Colors, and ambient light, can't be reached that way, so this module provides an
+//! [`AnimatableProperty`] for each of them instead. Colors are animated in linear RGB space.
+
+use core::any::TypeId;
+
+use bevy::{
+    animation::{
+        AnimationEntityMut, AnimationEvaluationError,
+        animation_curves::{AnimatableProperty, EvaluatorId},
+    },
+    color::{Color, LinearRgba},
+    reflect::Reflect,
+};
+
+use crate::light::{AmbientLight2d, Light2d, PointLight2d, SpotLight2d};
+
+/// Animates the color of a [`PointLight2d`].
+#[derive(Clone, Copy, Default, Reflect)]
+pub struct PointLight2dColor;
+
+impl AnimatableProperty for PointLight2dColor {
+    type Property = LinearRgba;
+
+    fn get_mut<'a>(
+        &self,
+        entity: &'a mut AnimationEntityMut,
+    ) -> Result<&'a mut LinearRgba, AnimationEvaluationError> {
+        let point_light = entity
+            .get_mut::<PointLight2d>()
+            .ok_or_else(component_not_present::<PointLight2d>)?;
+        Ok(as_linear_mut(&mut point_light.into_inner().color))
+    }
+
+    fn evaluator_id(&self) -> EvaluatorId<'_> {
+        EvaluatorId::Type(TypeId::of::<Self>())
+    }
+}
+
+/// Animates the color of a [`SpotLight2d`].
+#[derive(Clone, Copy, Default, Reflect)]
+pub struct SpotLight2dColor;
+
+impl AnimatableProperty for SpotLight2dColor {
+    type Property = LinearRgba;
+
+    fn get_mut<'a>(
+        &self,
+        entity: &'a mut AnimationEntityMut,
+    ) -> Result<&'a mut LinearRgba, AnimationEvaluationError> {
+        let spot_light = entity
+            .get_mut::<SpotLight2d>()
+            .ok_or_else(component_not_present::<SpotLight2d>)?;
+        Ok(as_linear_mut(&mut spot_light.into_inner().color))
+    }
+
+    fn evaluator_id(&self) -> EvaluatorId<'_> {
+        EvaluatorId::Type(TypeId::of::<Self>())
+    }
+}
+
+/// Animates the color of an [`AmbientLight2d`] entity, or a [`Light2d`] camera's ambient light.
+///
+/// A camera can only be animated once it has its own ambient light in
+/// [`Light2d::ambient_light`]. To continue smoothly from the
+/// [`GlobalAmbientLight2d`](crate::light::GlobalAmbientLight2d) resource, start the camera's
+/// ambient light from the resource's color and brightness.
+#[derive(Clone, Copy, Default, Reflect)]
+pub struct AmbientLight2dColor;
+
+impl AnimatableProperty for AmbientLight2dColor {
+    type Property = LinearRgba;
+
+    fn get_mut<'a>(
+        &self,
+        entity: &'a mut AnimationEntityMut,
+    ) -> Result<&'a mut LinearRgba, AnimationEvaluationError> {
+        Ok(as_linear_mut(&mut ambient_light_mut(entity)?.color))
+    }
+
+    fn evaluator_id(&self) -> EvaluatorId<'_> {
+        EvaluatorId::Type(TypeId::of::<Self>())
+    }
+}
+
+/// Animates the brightness of an [`AmbientLight2d`] entity, or a [`Light2d`] camera's ambient
+/// light. See [`AmbientLight2dColor`].
+#[derive(Clone, Copy, Default, Reflect)]
+pub struct AmbientLight2dBrightness;
+
+impl AnimatableProperty for AmbientLight2dBrightness {
+    type Property = f32;
+
+    fn get_mut<'a>(
+        &self,
+        entity: &'a mut AnimationEntityMut,
+    ) -> Result<&'a mut f32, AnimationEvaluationError> {
+        Ok(&mut ambient_light_mut(entity)?.brightness)
+    }
+
+    fn evaluator_id(&self) -> EvaluatorId<'_> {
+        EvaluatorId::Type(TypeId::of::<Self>())
+    }
+}
+
+/// Returns the entity's ambient light, preferring an [`AmbientLight2d`] component over a
+/// [`Light2d`] camera's override. A camera without an override has nothing to animate, as its
+/// ambient light comes from a resource.
+fn ambient_light_mut<'a>(
+    entity: &'a mut AnimationEntityMut,
+) -> Result<&'a mut AmbientLight2d, AnimationEvaluationError> {
+    if entity.contains::<AmbientLight2d>() {
+        let ambient_light = entity
+            .get_mut::<AmbientLight2d>()
+            .ok_or_else(component_not_present::<AmbientLight2d>)?;
+        return Ok(ambient_light.into_inner());
+    }
+
+    let light_2d = entity
+        .get_mut::<Light2d>()
+        .ok_or_else(component_not_present::<Light2d>)?;
+    light_2d.into_inner().ambient_light.as_mut().ok_or(
+        AnimationEvaluationError::PropertyNotPresent(TypeId::of::<AmbientLight2d>()),
+    )
+}
+
+fn component_not_present<C: 'static>() -> AnimationEvaluationError {
+    AnimationEvaluationError::ComponentNotPresent(TypeId::of::<C>())
+}
+
+/// Converts the given color to linear RGB in place, so it can be animated.
+fn as_linear_mut(color: &mut Color) -> &mut LinearRgba {
+    if !matches!(color, Color::LinearRgba(_)) {
+        *color = Color::LinearRgba(color.to_linear());
+    }
+    let Color::LinearRgba(linear) = color else {
+        unreachable!("color was converted to linear RGB above");
+    };
+    linear
+}
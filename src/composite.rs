@@ -0,0 +1,122 @@
+//! A module which contains how the light map is combined with the scene.
+
+use bevy::{
+    asset::Handle,
+    ecs::component::Component,
+    image::Image,
+    math::curve::UnevenSampleAutoCurve,
+    prelude::{ReflectComponent, ReflectDefault},
+    reflect::Reflect,
+};
+
+/// Changes how the light map is applied to the scene for a [`Light2d`](crate::light::Light2d)
+/// camera.
+///
+/// Without this component, the scene is multiplied by the light map in linear space. Different
+/// cameras can use different settings, such as a second camera giving a stylized night vision
+/// view of the same scene.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct LightComposite2d {
+    /// How the scene and the light map are combined.
+    pub mode: LightCompositeMode,
+    /// The color space the scene and the light map are combined in.
+    pub space: LightCompositeSpace,
+    /// Multiplies the light map before it's applied. This is combined with any
+    /// [`AutoExposure2d`](crate::exposure::AutoExposure2d).
+    pub exposure: f32,
+}
+
+impl Default for LightComposite2d {
+    fn default() -> Self {
+        Self {
+            mode: LightCompositeMode::Multiply,
+            space: LightCompositeSpace::Linear,
+            exposure: 1.0,
+        }
+    }
+}
+
+/// How the light map is combined with the scene. See [`LightComposite2d`].
+///
+/// In every mode, a light value of `1.0` leaves the scene unchanged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default)]
+pub enum LightCompositeMode {
+    /// The scene is multiplied by the light. Light brighter than `1.0` brightens the scene's
+    /// colors, but can't bring out color where the scene is black.
+    #[default]
+    Multiply,
+    /// A soft light blend, where darkness and light gently shift the scene's colors rather than
+    /// scaling them. Shadows keep some of the scene's detail.
+    SoftLight,
+    /// An overlay blend, which is harsher than [`LightCompositeMode::SoftLight`] and keeps more
+    /// of the scene's contrast.
+    Overlay,
+    /// The scene is multiplied by the light up to `1.0`, and light beyond that is added on top,
+    /// so bright lights wash out to white instead of saturating the scene's colors.
+    MultiplyAdditive,
+}
+
+/// The color space the light map is applied in. See [`LightComposite2d`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default)]
+pub enum LightCompositeSpace {
+    /// Physically based blending, where lighting behaves like real light.
+    #[default]
+    Linear,
+    /// Blending with the scene's gamma encoded (sRGB) colors, like image editors and many older
+    /// engines. Light falls off more quickly, and shadows are darker.
+    Gamma,
+}
+
+/// Color grades the light map of a [`Light2d`](crate::light::Light2d) camera before it's applied
+/// to the scene, such as tinting shadows blue and highlights orange.
+///
+/// Only the light is graded, so sprites keep their own colors. Grading covers light from `0.0` to
+/// `1.0`; brighter light keeps the grading at `1.0`, with the extra brightness added on top.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub enum LightColorGrading2d {
+    /// A 3D lookup table, indexed by the light's red, green and blue values.
+    ///
+    /// The image must have a [`TextureDimension::D3`](bevy::render::render_resource::TextureDimension::D3)
+    /// texture. No grading is applied until it's loaded.
+    Lut(Handle<Image>),
+    /// A curve for each of the light's channels.
+    Curves(LightColorCurves2d),
+}
+
+impl Default for LightColorGrading2d {
+    fn default() -> Self {
+        Self::Curves(LightColorCurves2d::default())
+    }
+}
+
+/// A curve for each channel of the light map, for [`LightColorGrading2d::Curves`].
+///
+/// Each curve maps the channel's value to its graded value, and is sampled from `0.0` to `1.0`.
+#[derive(Clone, Reflect)]
+#[reflect(Default)]
+pub struct LightColorCurves2d {
+    /// The curve applied to the light's red channel.
+    pub red: UnevenSampleAutoCurve<f32>,
+    /// The curve applied to the light's green channel.
+    pub green: UnevenSampleAutoCurve<f32>,
+    /// The curve applied to the light's blue channel.
+    pub blue: UnevenSampleAutoCurve<f32>,
+}
+
+impl Default for LightColorCurves2d {
+    /// Returns curves that leave the light unchanged.
+    fn default() -> Self {
+        let identity = UnevenSampleAutoCurve::new([(0.0, 0.0), (1.0, 1.0)])
+            .expect("the identity curve should be valid");
+
+        Self {
+            red: identity.clone(),
+            green: identity.clone(),
+            blue: identity,
+        }
+    }
+}
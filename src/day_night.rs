@@ -0,0 +1,270 @@
+//! A module which contains a day and night cycle, which drives the ambient light over time.
+
+use std::f32::consts::PI;
+
+use bevy::{
+    color::{Color, LinearRgba},
+    ecs::{
+        event::Event,
+        resource::Resource,
+        system::{Commands, Res, ResMut},
+    },
+    math::{
+        Dir2, Vec2,
+        curve::{Curve, UnevenSampleAutoCurve},
+    },
+    prelude::{ReflectDefault, ReflectResource},
+    reflect::Reflect,
+    time::Time,
+};
+
+/// The number of hours in a day.
+const HOURS_PER_DAY: f32 = 24.0;
+
+/// Tints the [`GlobalAmbientLight2d`](crate::light::GlobalAmbientLight2d) through a day and
+/// night cycle.
+///
+/// Time moves forward by itself, and the ambient light's color and brightness are sampled from
+/// curves by the time of day. The cycle also keeps track of the sun, which games can use for
+/// things like the direction of shadows or the intensity of a spot light.
+///
+/// [`Sunrise2d`] and [`Sunset2d`] are triggered as time passes the sunrise and sunset, and can be
+/// handled with observers. Jumping to a time with [`DayNightCycle2d::jump_to`] doesn't trigger
+/// them.
+///
+/// The cycle's ambient light is multiplied by the global ambient light when it's rendered, leaving
+/// the resource itself untouched. With the default white resource at a brightness of `1.0`, the
+/// cycle's curves are used as they are.
+///
+/// This resource isn't inserted by default. Cameras that override their ambient light with
+/// [`Light2d::ambient_light`](crate::light::Light2d::ambient_light) aren't affected by it.
+#[derive(Resource, Clone, Reflect)]
+#[reflect(Resource, Default)]
+pub struct DayNightCycle2d {
+    /// The time of day, in hours from `0.0` (midnight) up to `24.0`.
+    pub time_of_day: f32,
+    /// How long a full day lasts, in seconds.
+    pub day_length: f32,
+    /// How quickly time passes, as a multiple of [`DayNightCycle2d::day_length`]. A speed of
+    /// `2.0` makes a day pass in half the time.
+    pub speed: f32,
+    /// Whether time is stopped. Changing the time of day while paused is still shown straight
+    /// away.
+    pub paused: bool,
+    /// The time of day the sun rises, in hours.
+    pub sunrise: f32,
+    /// The time of day the sun sets, in hours.
+    pub sunset: f32,
+    /// The ambient light's color over the day, sampled by the time of day in hours.
+    pub color_curve: UnevenSampleAutoCurve<LinearRgba>,
+    /// The ambient light's brightness over the day, sampled by the time of day in hours.
+    pub brightness_curve: UnevenSampleAutoCurve<f32>,
+    /// The intensity of the sun at the middle of the day. See [`DayNightCycle2d::sun`].
+    pub sun_intensity: f32,
+}
+
+impl Default for DayNightCycle2d {
+    /// Returns a ten minute day, starting at noon, with a blue night and an orange sunrise and
+    /// sunset.
+    fn default() -> Self {
+        let night = Color::srgb(0.3, 0.35, 0.7).to_linear();
+        let sunrise = Color::srgb(1.0, 0.7, 0.5).to_linear();
+        let sunset = Color::srgb(1.0, 0.55, 0.35).to_linear();
+
+        Self {
+            time_of_day: 12.0,
+            day_length: 600.0,
+            speed: 1.0,
+            paused: false,
+            sunrise: 6.0,
+            sunset: 18.0,
+            color_curve: UnevenSampleAutoCurve::new([
+                (0.0, night),
+                (5.0, night),
+                (6.5, sunrise),
+                (9.0, LinearRgba::WHITE),
+                (16.0, LinearRgba::WHITE),
+                (18.0, sunset),
+                (20.0, night),
+                (24.0, night),
+            ])
+            .expect("the default color curve should be valid"),
+            brightness_curve: UnevenSampleAutoCurve::new([
+                (0.0, 0.15),
+                (5.0, 0.15),
+                (6.5, 0.6),
+                (9.0, 1.0),
+                (16.0, 1.0),
+                (18.0, 0.6),
+                (20.0, 0.15),
+                (24.0, 0.15),
+            ])
+            .expect("the default brightness curve should be valid"),
+            sun_intensity: 1.0,
+        }
+    }
+}
+
+impl DayNightCycle2d {
+    /// Moves to the given time of day, in hours, without triggering [`Sunrise2d`] or
+    /// [`Sunset2d`].
+    pub fn jump_to(&mut self, time_of_day: f32) {
+        self.time_of_day = time_of_day.rem_euclid(HOURS_PER_DAY);
+    }
+
+    /// Stops time from passing.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Lets time pass again after being paused.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Returns whether the sun is up.
+    pub fn is_day(&self) -> bool {
+        self.day_progress().is_some()
+    }
+
+    /// Returns the ambient light's color at the current time of day, multiplied by its
+    /// brightness.
+    pub fn ambient_light(&self) -> LinearRgba {
+        let color = self.color_curve.sample_clamped(self.time_of_day);
+        let brightness = self.brightness_curve.sample_clamped(self.time_of_day);
+        color * brightness.max(0.0)
+    }
+
+    /// Returns the sun while it's up, or `None` at night.
+    ///
+    /// The sun rises on the left of the screen and sets on the right, so its light travels to the
+    /// right in the morning, straight down at the middle of the day, and to the left in the
+    /// evening. Its intensity peaks at [`DayNightCycle2d::sun_intensity`] at the middle of the
+    /// day, fading out towards the horizon.
+    pub fn sun(&self) -> Option<Sun2d> {
+        let angle = self.day_progress()? * PI;
+
+        Some(Sun2d {
+            direction: Dir2::new(Vec2::new(angle.cos(), -angle.sin())).unwrap_or(Dir2::NEG_Y),
+            intensity: self.sun_intensity * angle.sin(),
+        })
+    }
+
+    /// How far the sun is through the day, from `0.0` at sunrise to `1.0` at sunset.
+    fn day_progress(&self) -> Option<f32> {
+        let day = (self.sunset - self.sunrise).rem_euclid(HOURS_PER_DAY);
+        let since_sunrise = (self.time_of_day - self.sunrise).rem_euclid(HOURS_PER_DAY);
+
+        (day > 0.0 && since_sunrise < day).then(|| since_sunrise / day)
+    }
+}
+
+/// The sun of a [`DayNightCycle2d`], as returned by [`DayNightCycle2d::sun`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sun2d {
+    /// The direction the sun's light travels in.
+    pub direction: Dir2,
+    /// The intensity of the sun's light.
+    pub intensity: f32,
+}
+
+/// Triggered when a [`DayNightCycle2d`] passes its sunrise.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Sunrise2d;
+
+/// Triggered when a [`DayNightCycle2d`] passes its sunset.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Sunset2d;
+
+pub(crate) fn advance_day_night_cycle(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut cycle: ResMut<DayNightCycle2d>,
+) {
+    if !cycle.paused && cycle.day_length > 0.0 {
+        let hours = time.delta_secs() * cycle.speed * HOURS_PER_DAY / cycle.day_length;
+        let start = cycle.time_of_day;
+        let end = start + hours;
+
+        if passes(start, end, cycle.sunrise) {
+            commands.trigger(Sunrise2d);
+        }
+        if passes(start, end, cycle.sunset) {
+            commands.trigger(Sunset2d);
+        }
+
+        cycle.time_of_day = end.rem_euclid(HOURS_PER_DAY);
+    }
+}
+
+/// Returns whether moving forward in time from `start` to `end` passes the given hour of the day.
+fn passes(start: f32, end: f32, hour: f32) -> bool {
+    if end <= start {
+        return false;
+    }
+
+    // The first time after the start that it's the given hour. Starting on the hour means it was
+    // passed by the previous step, so the next one is a day later.
+    let until = (hour - start).rem_euclid(HOURS_PER_DAY);
+    let next = start + if until > 0.0 { until } else { HOURS_PER_DAY };
+    next <= end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(time_of_day: f32, sunrise: f32, sunset: f32) -> DayNightCycle2d {
+        DayNightCycle2d {
+            time_of_day,
+            sunrise,
+            sunset,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn passes_hours_within_the_step() {
+        assert!(passes(5.5, 6.5, 6.0));
+        assert!(passes(5.5, 6.0, 6.0));
+        assert!(!passes(6.0, 6.5, 6.0));
+        assert!(!passes(6.5, 7.0, 6.0));
+        assert!(!passes(6.5, 6.5, 6.0));
+        assert!(!passes(6.5, 5.5, 6.0));
+    }
+
+    #[test]
+    fn passes_hours_across_midnight() {
+        assert!(passes(23.5, 24.5, 0.25));
+        assert!(passes(23.5, 24.5, 23.75));
+        assert!(!passes(23.5, 24.5, 1.0));
+        assert!(!passes(23.5, 24.5, 23.0));
+    }
+
+    #[test]
+    fn step_spanning_a_whole_day_passes_every_hour() {
+        for hour in [0.0, 6.0, 10.0, 18.0, 23.5] {
+            assert!(passes(10.0, 34.0, hour), "{hour}");
+            assert!(passes(10.0, 100.0, hour), "{hour}");
+        }
+    }
+
+    #[test]
+    fn day_progress_runs_from_sunrise_to_sunset() {
+        assert_eq!(cycle(6.0, 6.0, 18.0).day_progress(), Some(0.0));
+        assert_eq!(cycle(12.0, 6.0, 18.0).day_progress(), Some(0.5));
+        assert_eq!(cycle(18.0, 6.0, 18.0).day_progress(), None);
+        assert_eq!(cycle(3.0, 6.0, 18.0).day_progress(), None);
+        assert_eq!(cycle(12.0, 12.0, 12.0).day_progress(), None);
+    }
+
+    #[test]
+    fn day_progress_across_midnight() {
+        // The sun rises late in the evening and sets early the next morning.
+        assert_eq!(cycle(22.0, 22.0, 4.0).day_progress(), Some(0.0));
+        assert_eq!(cycle(1.0, 22.0, 4.0).day_progress(), Some(0.5));
+        assert_eq!(cycle(4.0, 22.0, 4.0).day_progress(), None);
+        assert_eq!(cycle(12.0, 22.0, 4.0).day_progress(), None);
+        assert!(!cycle(12.0, 22.0, 4.0).is_day());
+    }
+}
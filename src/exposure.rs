@@ -0,0 +1,38 @@
+//! A module which contains exposure, which adjusts how bright the lit scene appears.
+
+use bevy::{
+    ecs::component::Component,
+    prelude::{ReflectComponent, ReflectDefault},
+    reflect::Reflect,
+};
+
+/// Adjusts the exposure of a [`Light2d`](crate::light::Light2d) camera to the brightness of the
+/// scene, the way eyes adapt to the dark.
+///
+/// The average luminance of the light map is measured each frame, and the exposure moves towards
+/// the value that brings it back to `1.0`. Walking from a dark cave into daylight will briefly
+/// blow out the scene before it adapts. The exposure multiplies the light falling on the scene,
+/// so [`Unlit2d`](crate::sprite::Unlit2d) sprites aren't affected by it.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct AutoExposure2d {
+    /// The lowest exposure, used in the brightest scenes.
+    pub min_exposure: f32,
+    /// The highest exposure, used in the darkest scenes.
+    pub max_exposure: f32,
+    /// How quickly the exposure rises when the scene gets darker. Higher values adapt faster.
+    pub brighten_speed: f32,
+    /// How quickly the exposure falls when the scene gets brighter. Higher values adapt faster.
+    pub darken_speed: f32,
+}
+
+impl Default for AutoExposure2d {
+    fn default() -> Self {
+        Self {
+            min_exposure: 0.25,
+            max_exposure: 4.0,
+            brighten_speed: 1.0,
+            darken_speed: 3.0,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! A module which contains components for fog, dust and smoke that light scatters through.
+
+use bevy::{
+    asset::Handle,
+    camera::visibility::Visibility,
+    color::Color,
+    ecs::component::Component,
+    image::Image,
+    math::Vec2,
+    prelude::{ReflectComponent, ReflectDefault},
+    reflect::Reflect,
+    transform::components::Transform,
+};
+
+/// Fills the view of a [`Light2d`](crate::light::Light2d) camera with fog, which point and spot
+/// lights scatter through, making their beams visible.
+///
+/// Light is gathered from the fog along the ray between each pixel and each light, so a patch of
+/// dense fog near a light streaks shafts outwards past it, even into thinner fog. Occluders cut
+/// those rays off along with the light itself. Rays are sampled at a fixed number of points, so
+/// fog much smaller than a light's radius may shimmer. The scene behind thick fog is faded out,
+/// and replaced by the light scattered towards the camera.
+///
+/// The density of the fog is the sum of [`Fog2d::density`], the optional [`Fog2d::texture`] and
+/// any [`FogVolume2d`] entities.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct Fog2d {
+    /// The color light takes on as it scatters through the fog.
+    pub color: Color,
+    /// How thick the fog is everywhere. A value of `0.0` only has fog where the
+    /// [`Fog2d::texture`] or a [`FogVolume2d`] adds some.
+    pub density: f32,
+    /// An optional texture which varies the density of the fog across the world.
+    pub texture: Option<FogTexture2d>,
+}
+
+impl Default for Fog2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            density: 0.0,
+            texture: None,
+        }
+    }
+}
+
+/// A texture which is tiled across the world to add density to [`Fog2d`], such as drifting
+/// smoke or patches of mist.
+#[derive(Clone, Reflect)]
+#[reflect(Default)]
+pub struct FogTexture2d {
+    /// The texture to tile. Its red channel is multiplied by [`FogTexture2d::density`].
+    pub image: Handle<Image>,
+    /// The size of each tile of the texture, in world units.
+    pub size: Vec2,
+    /// The world position of the texture's origin. Move this over time to make the fog drift.
+    pub offset: Vec2,
+    /// How much density the brightest parts of the texture add.
+    pub density: f32,
+}
+
+impl Default for FogTexture2d {
+    fn default() -> Self {
+        Self {
+            image: Handle::default(),
+            size: Vec2::splat(512.0),
+            offset: Vec2::ZERO,
+            density: 1.0,
+        }
+    }
+}
+
+/// A rectangular region of fog, such as smoke in a single room or dust in a shaft of sunlight.
+///
+/// The volume is centered on the entity's transform, and adds its density to the fog of every
+/// [`Light2d`](crate::light::Light2d) camera.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+#[require(Transform, Visibility)]
+pub struct FogVolume2d {
+    /// Half the width and height of the volume.
+    pub half_size: Vec2,
+    /// How thick the fog is inside the volume.
+    pub density: f32,
+    /// How far in from the volume's edges the fog fades in, in world units.
+    pub falloff: f32,
+}
+
+impl Default for FogVolume2d {
+    fn default() -> Self {
+        Self {
+            half_size: Vec2::splat(50.0),
+            density: 1.0,
+            falloff: 0.0,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! A module which contains global illumination, where light bounces off surfaces in the scene.
+
+use bevy::{
+    ecs::component::Component,
+    prelude::{ReflectComponent, ReflectDefault},
+    reflect::Reflect,
+};
+
+/// Enables global illumination for a [`Light2d`](crate::light::Light2d) camera, so lit occluders
+/// and emissive sprites light their surroundings, such as a red wall next to a torch casting red
+/// light into the room.
+///
+/// Lighting is gathered by a grid of probes at a reduced resolution, each casting rays through the
+/// occluders' signed distance field. A ray hitting an occluder picks up the light falling on the
+/// side it hit, tinted by the color of the scene there, while rays passing over
+/// [`Emissive2d`](crate::sprite::Emissive2d) sprites pick up their emission. This adds a single
+/// bounce of light on top of the direct lighting.
+///
+/// This is a single grid of probes rather than radiance cascades, so every probe traces the same
+/// number of rays however far the light comes from. Distant sources are sampled sparsely, and
+/// rays give up after a fixed number of steps, which can fall short of
+/// [`GlobalIllumination2d::max_distance`] when they pass close to occluders.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct GlobalIllumination2d {
+    /// How strongly bounced light contributes to the scene.
+    pub intensity: f32,
+    /// The spacing between probes, in screen pixels. Higher values are faster, but blurrier.
+    pub probe_spacing: u32,
+    /// How many rays each probe casts. More rays reduce noise, at a higher cost.
+    pub ray_count: u32,
+    /// How far rays travel before giving up, in world units. Rays near occluders take smaller
+    /// steps, and may give up sooner.
+    pub max_distance: f32,
+}
+
+impl Default for GlobalIllumination2d {
+    fn default() -> Self {
+        Self {
+            intensity: 1.0,
+            probe_spacing: 4,
+            ray_count: 16,
+            max_distance: 500.0,
+        }
+    }
+}
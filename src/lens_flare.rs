@@ -0,0 +1,88 @@
+//! A module which contains lens flares for bright lights.
+
+use bevy::{
+    asset::Handle,
+    color::Color,
+    ecs::component::Component,
+    image::Image,
+    prelude::{ReflectComponent, ReflectDefault},
+    reflect::Reflect,
+};
+
+/// Draws a lens flare over the screen for a [`PointLight2d`](crate::light::PointLight2d) or
+/// [`SpotLight2d`](crate::light::SpotLight2d), such as the glare of a star in space.
+///
+/// Each [`LensFlareElement2d`] is drawn along the line from the light to the center of the screen,
+/// tinted by the light's color. The flare fades out when the light's center is inside an occluder,
+/// or moves off screen.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct LensFlare2d {
+    /// The sprites making up the flare.
+    pub elements: Vec<LensFlareElement2d>,
+    /// How bright the flare is. Each element's color is multiplied by this value.
+    pub intensity: f32,
+}
+
+impl Default for LensFlare2d {
+    /// Returns a flare with a glow at the light, and a few rings towards and past the center of
+    /// the screen.
+    fn default() -> Self {
+        Self {
+            elements: vec![
+                LensFlareElement2d {
+                    position: 0.0,
+                    size: 96.0,
+                    ..Default::default()
+                },
+                LensFlareElement2d {
+                    position: 0.6,
+                    size: 24.0,
+                    color: Color::srgba(1.0, 1.0, 1.0, 0.3),
+                    ..Default::default()
+                },
+                LensFlareElement2d {
+                    position: 1.3,
+                    size: 48.0,
+                    color: Color::srgba(1.0, 1.0, 1.0, 0.2),
+                    ..Default::default()
+                },
+                LensFlareElement2d {
+                    position: 1.8,
+                    size: 16.0,
+                    color: Color::srgba(1.0, 1.0, 1.0, 0.3),
+                    ..Default::default()
+                },
+            ],
+            intensity: 1.0,
+        }
+    }
+}
+
+/// A single sprite within a [`LensFlare2d`].
+#[derive(Clone, Reflect)]
+#[reflect(Default)]
+pub struct LensFlareElement2d {
+    /// The image to draw. When `None`, a soft round glow is drawn instead.
+    pub image: Option<Handle<Image>>,
+    /// Where the element sits along the line from the light to the center of the screen.
+    ///
+    /// A value of `0.0` places it over the light, `1.0` at the center of the screen, and `2.0` at
+    /// the light's reflection on the other side of the screen.
+    pub position: f32,
+    /// The width and height of the element, in screen pixels.
+    pub size: f32,
+    /// The element's color tint. The alpha channel controls how strongly it's drawn.
+    pub color: Color,
+}
+
+impl Default for LensFlareElement2d {
+    fn default() -> Self {
+        Self {
+            image: None,
+            position: 0.0,
+            size: 32.0,
+            color: Color::WHITE,
+        }
+    }
+}
@@ -2,16 +2,47 @@
 #![doc = include_str!("../README.md")]
 #![expect(deprecated)]
 
+#[cfg(feature = "animation")]
+pub mod animation;
+pub mod composite;
+pub mod day_night;
+pub mod exposure;
+pub mod fog;
+pub mod global_illumination;
+pub mod lens_flare;
 pub mod light;
+pub mod modulation;
 pub mod occluder;
 pub mod plugin;
+pub mod reflection;
 mod render;
+pub mod sprite;
+pub mod transient;
 
 /// A module which exports commonly used dependencies.
 pub mod prelude {
+    #[cfg(feature = "animation")]
+    pub use crate::animation::{
+        AmbientLight2dBrightness, AmbientLight2dColor, PointLight2dColor, SpotLight2dColor,
+    };
+    pub use crate::composite::{
+        LightColorCurves2d, LightColorGrading2d, LightComposite2d, LightCompositeMode,
+        LightCompositeSpace,
+    };
+    pub use crate::day_night::{DayNightCycle2d, Sun2d, Sunrise2d, Sunset2d};
+    pub use crate::exposure::AutoExposure2d;
+    pub use crate::fog::{Fog2d, FogTexture2d, FogVolume2d};
+    pub use crate::global_illumination::GlobalIllumination2d;
+    pub use crate::lens_flare::{LensFlare2d, LensFlareElement2d};
     pub use crate::light::{
-        AmbientLight2d, Light2d, PointLight2d, PointLight2dBundle, SpotLight2d,
+        AmbientLight2d, AmbientLight2dMapping, AmbientLight2dSource, AmbientOcclusion2d,
+        AmbientZone2d, AmbientZone2dShape, GlobalAmbientLight2d, Light2d, LightEmitter2d,
+        LightEmitterBatch2d, LightHalo2d, PointLight2d, PointLight2dBundle, SpotLight2d,
     };
+    pub use crate::modulation::{LightFlicker2d, LightPulse2d};
     pub use crate::occluder::{LightOccluder2d, LightOccluder2dBundle, LightOccluder2dShape};
-    pub use crate::plugin::Light2dPlugin;
+    pub use crate::plugin::{Light2dPlugin, Light2dSystems};
+    pub use crate::reflection::{LightBeamPath2d, LightBeams2d};
+    pub use crate::sprite::{Emissive2d, NormalMap2d, Specular2d, Unlit2d};
+    pub use crate::transient::{LightFlashCommandsExt, TransientLight2d};
 }
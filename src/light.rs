@@ -1,11 +1,14 @@
 //! A module which contains lighting components.
 
 use bevy::{
+    asset::Handle,
     camera::visibility,
     camera::visibility::{InheritedVisibility, ViewVisibility, Visibility, VisibilityClass},
     color::Color,
-    ecs::{bundle::Bundle, component::Component},
-    prelude::{ReflectComponent, ReflectDefault},
+    ecs::{bundle::Bundle, component::Component, resource::Resource},
+    image::Image,
+    math::{Rect, Vec2},
+    prelude::{ReflectComponent, ReflectDefault, ReflectResource},
     reflect::Reflect,
     render::sync_world::SyncToRenderWorld,
     transform::components::{GlobalTransform, Transform},
@@ -14,10 +17,18 @@ use bevy::{
 /// A "marker" component to be used with a `Camera2d`.
 ///
 /// 2D lighting effects will only run for cameras that have this component.
-#[derive(Component, Default)]
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
 pub struct Light2d {
-    /// The ambight light to apply to the scene.
-    pub ambient_light: AmbientLight2d,
+    /// Overrides the [`GlobalAmbientLight2d`] resource for this camera. Either way, any
+    /// [`AmbientLight2d`] entities are added on top.
+    pub ambient_light: Option<AmbientLight2d>,
+    /// How far light from [`Emissive2d`](crate::sprite::Emissive2d) sprites spreads beyond the
+    /// sprite, in world units. A value of `0.0` keeps emission within the sprite's shape.
+    pub emissive_blur: f32,
+    /// Darkens ambient light close to occluders, grounding sprites next to walls. Disabled when
+    /// `None`.
+    pub ambient_occlusion: Option<AmbientOcclusion2d>,
 }
 
 /// A light that provides illumination in all directions.
@@ -49,6 +60,23 @@ pub struct PointLight2d {
     pub falloff: f32,
     /// Whether the light should cast shadows.
     pub cast_shadows: bool,
+    /// How high above the ground the light sits, in world units.
+    ///
+    /// A light that's higher than an occluder (see [`LightOccluder2d::height`]) shines over it,
+    /// and the occluder's shadow has a finite length.
+    ///
+    /// [`LightOccluder2d::height`]: crate::occluder::LightOccluder2d::height
+    pub height: f32,
+    /// An optional glow around the light, which is visible even where the scene is black.
+    pub halo: Option<LightHalo2d>,
+    /// The most reflective occluders (see [`LightOccluder2d::reflectivity`]) the light bounces
+    /// off in a row. Only lights that cast shadows are reflected.
+    ///
+    /// Each reflection is rendered as an extra light, so keep this low where there are many
+    /// mirrors.
+    ///
+    /// [`LightOccluder2d::reflectivity`]: crate::occluder::LightOccluder2d::reflectivity
+    pub max_reflections: u32,
 }
 
 impl Default for PointLight2d {
@@ -60,6 +88,9 @@ impl Default for PointLight2d {
             radius: 0.5,
             falloff: 0.0,
             cast_shadows: false,
+            height: 0.0,
+            halo: None,
+            max_reflections: 1,
         }
     }
 }
@@ -96,6 +127,13 @@ pub struct SpotLight2d {
     pub source_width: f32,
     /// Whether the light should cast shadows.
     pub cast_shadows: bool,
+    /// How high above the ground the light sits, in world units. See [`PointLight2d::height`].
+    pub height: f32,
+    /// An optional glow around the light's center. See [`PointLight2d::halo`].
+    pub halo: Option<LightHalo2d>,
+    /// The most reflective occluders the light bounces off in a row. See
+    /// [`PointLight2d::max_reflections`].
+    pub max_reflections: u32,
 }
 
 impl Default for SpotLight2d {
@@ -110,6 +148,77 @@ impl Default for SpotLight2d {
             outer_angle: -90.,
             source_width: 1.,
             cast_shadows: false,
+            height: 0.0,
+            halo: None,
+            max_reflections: 1,
+        }
+    }
+}
+
+/// A glow drawn around a [`PointLight2d`] or [`SpotLight2d`], such as lamps in the night sky or
+/// fireflies over a void.
+///
+/// Unlike the light itself, which brightens the scene beneath it, the halo is added on top of the
+/// final image. It's hidden when the light's center is inside an occluder, which is only checked
+/// while the center is on screen.
+#[derive(Clone, Reflect)]
+#[reflect(Default)]
+pub struct LightHalo2d {
+    /// How bright the halo is at the light's center.
+    pub intensity: f32,
+    /// The radius of the halo, in world units.
+    pub size: f32,
+}
+
+impl Default for LightHalo2d {
+    fn default() -> Self {
+        Self {
+            intensity: 1.0,
+            size: 32.0,
+        }
+    }
+}
+
+/// A batch of small lights that move together, such as sparks, fireflies or bullets.
+///
+/// Each [`LightEmitter2d`] in the batch is positioned relative to the entity's transform. Emitters
+/// don't cast shadows, and every batch is uploaded to the GPU as part of a single buffer, making
+/// them much cheaper than spawning an entity with a [`PointLight2d`] for each light.
+///
+/// On WebGL2, the number of emitters that can be rendered at once is limited to 82 (shared with
+/// [`TransientLight2d`](crate::transient::TransientLight2d)).
+#[derive(Component, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+#[require(Transform, Visibility)]
+pub struct LightEmitterBatch2d {
+    /// The lights in the batch.
+    pub emitters: Vec<LightEmitter2d>,
+    /// How quickly illumination from each light should deteriorate over distance.
+    pub falloff: f32,
+}
+
+/// A single light within a [`LightEmitterBatch2d`].
+#[derive(Clone, Reflect)]
+#[reflect(Default)]
+pub struct LightEmitter2d {
+    /// The light's position, relative to the batch's transform.
+    pub offset: Vec2,
+    /// The light's color tint.
+    pub color: Color,
+    /// The radius of the light, scaled by the batch's transform. Illumination will only occur
+    /// within the light's radius.
+    pub radius: f32,
+    /// The intensity of the light. The light's attenutation is multiplied by this value.
+    pub intensity: f32,
+}
+
+impl Default for LightEmitter2d {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            color: Color::WHITE,
+            radius: 0.5,
+            intensity: 1.0,
         }
     }
 }
@@ -135,11 +244,20 @@ pub struct PointLight2dBundle {
     pub view_visibility: ViewVisibility,
 }
 
-/// A component representing how much ambient light to apply to a `Camera2d`.
+/// How much ambient light to apply to a [`Light2d`] camera.
 ///
 /// - For a darker scene, use a brightness value between `0.0` and `1.0`.
 /// - For a brighter scene, use a brightness greater than `1.0`.
 /// - A brightness value of `0.0` will result in a completely black scene.
+///
+/// This can be set for a single camera with [`Light2d::ambient_light`], replacing the
+/// [`GlobalAmbientLight2d`] resource. As a component on any other entity, it's added on top of
+/// every camera's ambient light, so systems such as weather and time of day can each contribute
+/// their own ambient light without sharing a single value.
+///
+/// Because entities only add light, they can't darken the scene below the camera's ambient
+/// light, and the resource has a brightness of `1.0` by default. To darken a scene with ambient
+/// light entities, lower the [`GlobalAmbientLight2d`] brightness first.
 #[derive(Component, Clone, Reflect)]
 #[reflect(Component, Default)]
 pub struct AmbientLight2d {
@@ -148,6 +266,9 @@ pub struct AmbientLight2d {
     /// The brightness of the ambient light. This value is multiplied against the linear RGB
     /// representation of the ambient light's color.
     pub brightness: f32,
+    /// Where the ambient light varies across the view, such as a texture or a gradient. This is
+    /// multiplied by the color and brightness.
+    pub source: AmbientLight2dSource,
 }
 
 impl Default for AmbientLight2d {
@@ -157,6 +278,158 @@ impl Default for AmbientLight2d {
         Self {
             color: Color::WHITE,
             brightness: 1.0,
+            source: AmbientLight2dSource::Uniform,
+        }
+    }
+}
+
+/// How an [`AmbientLight2d`] varies across the view.
+///
+/// Each view shades at most one texture and one gradient. If several [`AmbientLight2d`] entities
+/// have the same kind of source, only one of them is used; their colors are still added together.
+#[derive(Clone, Default, Reflect)]
+#[reflect(Default)]
+pub enum AmbientLight2dSource {
+    /// The same ambient light everywhere.
+    #[default]
+    Uniform,
+    /// Ambient light sampled from a texture, such as painterly colored darkness.
+    Texture {
+        /// The texture to sample.
+        image: Handle<Image>,
+        /// Where the texture is placed. In world space, it's tiled beyond its rectangle.
+        mapping: AmbientLight2dMapping,
+    },
+    /// A vertical gradient from the sky down to the ground, such as the water getting darker the
+    /// deeper it is.
+    Gradient {
+        /// The color at the top of the gradient.
+        sky: Color,
+        /// The color at the bottom of the gradient.
+        ground: Color,
+        /// Where the gradient is placed. In world space, the top and bottom of the rectangle set
+        /// where it starts and ends, and it keeps its end colors beyond them.
+        mapping: AmbientLight2dMapping,
+    },
+}
+
+/// Where an [`AmbientLight2dSource`] is placed.
+#[derive(Clone, Default, Reflect)]
+#[reflect(Default)]
+pub enum AmbientLight2dMapping {
+    /// Stretched over the view, so it moves with the camera.
+    #[default]
+    Screen,
+    /// Stretched over a rectangle in world space.
+    World(Rect),
+}
+
+/// The ambient light for every [`Light2d`] camera, unless overridden by
+/// [`Light2d::ambient_light`]. [`AmbientLight2d`] entities are added on top of it.
+///
+/// This is inserted by [`Light2dPlugin`](crate::plugin::Light2dPlugin) with a brightness of `1.0`.
+#[derive(Resource, Clone, Reflect)]
+#[reflect(Resource, Default)]
+pub struct GlobalAmbientLight2d {
+    /// The ambient light's color tint.
+    pub color: Color,
+    /// The brightness of the ambient light. See [`AmbientLight2d::brightness`].
+    pub brightness: f32,
+}
+
+impl Default for GlobalAmbientLight2d {
+    /// Return a white ambient light with a brightness of `1.0` (which is effectively
+    /// the same as not having any ambient light).
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            brightness: 1.0,
+        }
+    }
+}
+
+/// An area with its own ambient light, such as a dark building interior in a bright outdoor
+/// scene.
+///
+/// Inside the zone, ambient light is replaced with the zone's own, blending back to the
+/// surrounding ambient light over the blend distance, so a cave mouth can darken gradually.
+/// Where zones overlap, the zone with the highest z translation wins.
+///
+/// Zones follow their entity's translation, rotation and scale. Circles stay round, scaled by the
+/// larger axis. Up to 16 zones can be visible at once, with up to 64 polygon vertices between
+/// them.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+#[require(Transform, Visibility)]
+pub struct AmbientZone2d {
+    /// The shape of the zone.
+    pub shape: AmbientZone2dShape,
+    /// The ambient light's color tint within the zone.
+    pub color: Color,
+    /// The brightness of the ambient light within the zone. See [`AmbientLight2d::brightness`].
+    pub brightness: f32,
+    /// How far outside the zone's shape its ambient light fades out, in world units.
+    pub blend_distance: f32,
+}
+
+impl Default for AmbientZone2d {
+    fn default() -> Self {
+        Self {
+            shape: AmbientZone2dShape::default(),
+            color: Color::WHITE,
+            brightness: 1.0,
+            blend_distance: 0.0,
+        }
+    }
+}
+
+/// Shape data for an [`AmbientZone2d`].
+#[derive(Clone, Reflect)]
+#[reflect(Default)]
+pub enum AmbientZone2dShape {
+    /// A rectangular zone.
+    Rectangle {
+        /// Half of the width and height of the rectangle.
+        half_size: Vec2,
+    },
+    /// A circular zone.
+    Circle {
+        /// The radius of the circle.
+        radius: f32,
+    },
+    /// A polygonal zone, which may be concave.
+    Polygon {
+        /// The polygon's corners, in the zone's local space.
+        vertices: Vec<Vec2>,
+    },
+}
+
+impl Default for AmbientZone2dShape {
+    fn default() -> Self {
+        Self::Rectangle {
+            half_size: Vec2::splat(50.0),
+        }
+    }
+}
+
+/// Ambient occlusion for a [`Light2d`] camera, which darkens ambient light close to occluders.
+///
+/// Occlusion is estimated from the distance to occluders at a few points around each pixel, so
+/// corners are darker than open walls. Only ambient light is affected.
+#[derive(Clone, Reflect)]
+#[reflect(Default)]
+pub struct AmbientOcclusion2d {
+    /// How far from occluders the darkening reaches, in world units.
+    pub radius: f32,
+    /// How dark fully occluded areas get, from `0.0` (no darkening) to `1.0` (black).
+    pub strength: f32,
+}
+
+impl Default for AmbientOcclusion2d {
+    fn default() -> Self {
+        Self {
+            radius: 24.0,
+            strength: 0.5,
         }
     }
 }
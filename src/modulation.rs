@@ -0,0 +1,265 @@
+//! A module which contains components that animate lights over time.
+
+use bevy::{
+    color::{Color, ColorToComponents, LinearRgba, Mix},
+    ecs::{component::Component, system::Query, system::Res},
+    prelude::{ReflectComponent, ReflectDefault},
+    reflect::Reflect,
+    time::Time,
+};
+
+/// Makes a [`PointLight2d`](crate::light::PointLight2d) or
+/// [`SpotLight2d`](crate::light::SpotLight2d) flicker, like a candle or a torch.
+///
+/// The flicker is driven by deterministic noise sampled from [`Time`], so it's independent of
+/// the frame rate, and will pause along with virtual time. Two lights with the same seed will
+/// flicker in unison.
+///
+/// The light's own properties are left untouched; the flicker is applied when the light is
+/// extracted for rendering.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+#[require(LightModulation2d)]
+pub struct LightFlicker2d {
+    /// How much the light's intensity varies, as a fraction of its intensity.
+    ///
+    /// An amplitude of `0.2` will vary the intensity by up to 20% in either direction.
+    pub amplitude: f32,
+    /// How much the light's radius varies, as a fraction of its radius.
+    pub radius_amplitude: f32,
+    /// How quickly the light flickers, in changes per second.
+    pub frequency: f32,
+    /// The seed used to generate the flicker's noise.
+    pub seed: u32,
+}
+
+impl Default for LightFlicker2d {
+    fn default() -> Self {
+        Self {
+            amplitude: 0.2,
+            radius_amplitude: 0.0,
+            frequency: 8.0,
+            seed: 0,
+        }
+    }
+}
+
+/// Makes a [`PointLight2d`](crate::light::PointLight2d) or
+/// [`SpotLight2d`](crate::light::SpotLight2d) pulse smoothly, like a beacon or an alarm.
+///
+/// Like [`LightFlicker2d`], the pulse is driven by [`Time`], and the light's own properties are
+/// left untouched. Both components can be used on the same light.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+#[require(LightModulation2d)]
+pub struct LightPulse2d {
+    /// How much the light's intensity varies, as a fraction of its intensity.
+    pub amplitude: f32,
+    /// How much the light's radius varies, as a fraction of its radius.
+    pub radius_amplitude: f32,
+    /// The number of pulses per second.
+    pub frequency: f32,
+    /// Offsets the pulse, as a fraction of a single pulse.
+    ///
+    /// Useful for keeping several pulsing lights out of step with each other.
+    pub phase: f32,
+    /// An optional color the light is tinted towards at the peak of each pulse.
+    pub color: Option<Color>,
+}
+
+impl Default for LightPulse2d {
+    fn default() -> Self {
+        Self {
+            amplitude: 0.5,
+            radius_amplitude: 0.0,
+            frequency: 1.0,
+            phase: 0.0,
+            color: None,
+        }
+    }
+}
+
+/// The combined effect of a light's [`LightFlicker2d`] and [`LightPulse2d`] components.
+///
+/// This is inserted automatically and updated every frame; it shouldn't generally be written to
+/// directly.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct LightModulation2d {
+    /// The value the light's intensity is multiplied by.
+    pub intensity: f32,
+    /// The value the light's radius is multiplied by.
+    pub radius: f32,
+    /// The value the light's color is multiplied by.
+    pub color: LinearRgba,
+}
+
+impl Default for LightModulation2d {
+    /// Returns a modulation that leaves the light unchanged.
+    fn default() -> Self {
+        Self {
+            intensity: 1.0,
+            radius: 1.0,
+            color: LinearRgba::WHITE,
+        }
+    }
+}
+
+impl LightModulation2d {
+    /// Returns the given light color, multiplied by this modulation's color.
+    pub(crate) fn tint(&self, color: Color) -> LinearRgba {
+        multiply(color.to_linear(), self.color)
+    }
+}
+
+pub(crate) fn modulate_lights(
+    time: Res<Time>,
+    mut lights: Query<(
+        &mut LightModulation2d,
+        Option<&LightFlicker2d>,
+        Option<&LightPulse2d>,
+    )>,
+) {
+    let elapsed = time.elapsed_secs_f64();
+
+    for (mut modulation, flicker, pulse) in &mut lights {
+        let mut next = LightModulation2d::default();
+
+        if let Some(flicker) = flicker {
+            let noise = fractal_noise(flicker.seed, elapsed * flicker.frequency as f64);
+            next.intensity *= 1.0 + flicker.amplitude * noise;
+            next.radius *= 1.0 + flicker.radius_amplitude * noise;
+        }
+
+        if let Some(pulse) = pulse {
+            let cycles = elapsed * pulse.frequency as f64 + pulse.phase as f64;
+            // A sine wave remapped to 0..1, starting at the trough of the pulse.
+            let wave = (0.5 - 0.5 * (cycles.fract() * std::f64::consts::TAU).cos()) as f32;
+            let signed_wave = wave * 2.0 - 1.0;
+            next.intensity *= 1.0 + pulse.amplitude * signed_wave;
+            next.radius *= 1.0 + pulse.radius_amplitude * signed_wave;
+
+            if let Some(color) = pulse.color {
+                let tint = LinearRgba::WHITE.mix(&color.to_linear(), wave);
+                next.color = multiply(next.color, tint);
+            }
+        }
+
+        next.intensity = next.intensity.max(0.0);
+        next.radius = next.radius.max(0.0);
+
+        *modulation = next;
+    }
+}
+
+fn multiply(a: LinearRgba, b: LinearRgba) -> LinearRgba {
+    LinearRgba::from_vec4(a.to_vec4() * b.to_vec4())
+}
+
+/// Returns smooth noise in the range `-1.0..=1.0`, made up of two octaves of value noise.
+fn fractal_noise(seed: u32, x: f64) -> f32 {
+    let low = value_noise(seed, x);
+    let high = value_noise(seed.wrapping_add(0x9e37_79b9), x * 2.31 + 17.0);
+    (low * 0.65 + high * 0.35) * 2.0 - 1.0
+}
+
+/// Returns smooth noise in the range `0.0..=1.0`, interpolating between random values placed at
+/// each whole number.
+fn value_noise(seed: u32, x: f64) -> f32 {
+    let floor = x.floor();
+    let t = (x - floor) as f32;
+    let i = floor as i64;
+
+    let a = hash(seed, i);
+    let b = hash(seed, i.wrapping_add(1));
+    let t = t * t * (3.0 - 2.0 * t);
+
+    a + (b - a) * t
+}
+
+/// Hashes a seed and a lattice point into a value in the range `0.0..=1.0`.
+fn hash(seed: u32, i: i64) -> f32 {
+    let mut x = (i as u64) ^ ((seed as u64) << 32);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    x ^= x >> 33;
+    (x >> 40) as f32 / (1u64 << 24) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use bevy::{ecs::system::RunSystemOnce, ecs::world::World, math::Vec4};
+
+    use super::*;
+
+    #[test]
+    fn noise_is_deterministic() {
+        // Changing these values changes how every seeded light flickers.
+        assert!((hash(7, 42) - 0.010_389_03).abs() < 1e-6);
+        assert!((value_noise(7, 3.25) - 0.157_885_97).abs() < 1e-6);
+        assert!((fractal_noise(7, 12.5) - 0.242_751_84).abs() < 1e-6);
+        assert!((fractal_noise(0, 0.0) + 0.602_834_1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn noise_is_continuous() {
+        for i in 0..1000 {
+            let x = i as f64 * 0.37;
+            let step = (fractal_noise(3, x + 1e-3) - fractal_noise(3, x)).abs();
+            assert!(step < 1e-2, "noise jumps by {step} at {x}");
+        }
+    }
+
+    #[test]
+    fn noise_depends_on_seed() {
+        assert_ne!(fractal_noise(1, 12.5), fractal_noise(2, 12.5));
+    }
+
+    #[test]
+    fn noise_stays_in_range() {
+        for i in 0..1000 {
+            let x = i as f64 * 0.37;
+            assert!((0.0..=1.0).contains(&value_noise(3, x)));
+            assert!((-1.0..=1.0).contains(&fractal_noise(3, x)));
+        }
+    }
+
+    #[test]
+    fn value_noise_hits_lattice_values() {
+        assert_eq!(value_noise(5, 4.0), hash(5, 4));
+    }
+
+    #[test]
+    fn pulse_peaks_half_way_through_each_pulse() {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(500));
+        world.insert_resource(time);
+
+        let light = world
+            .spawn(LightPulse2d {
+                amplitude: 0.5,
+                radius_amplitude: 0.25,
+                frequency: 1.0,
+                phase: 0.0,
+                color: Some(Color::linear_rgb(1.0, 0.5, 0.0)),
+            })
+            .id();
+
+        world.run_system_once(modulate_lights).unwrap();
+
+        let modulation = world.get::<LightModulation2d>(light).unwrap();
+        assert!((modulation.intensity - 1.5).abs() < 1e-5);
+        assert!((modulation.radius - 1.25).abs() < 1e-5);
+        assert!(
+            modulation
+                .color
+                .to_vec4()
+                .abs_diff_eq(Vec4::new(1.0, 0.5, 0.0, 1.0), 1e-5)
+        );
+    }
+}
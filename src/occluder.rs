@@ -2,6 +2,7 @@
 
 use bevy::{
     camera::visibility::{self, InheritedVisibility, ViewVisibility, Visibility, VisibilityClass},
+    color::Color,
     ecs::{bundle::Bundle, component::Component},
     math::Vec2,
     render::sync_world::SyncToRenderWorld,
@@ -11,12 +12,55 @@ use bevy::{
 /// A light occluder that prevents light passing through it, casting shadows.
 ///
 /// This is commonly used as a component within [`LightOcluder2dBundle`].
-#[derive(Default, Component)]
+#[derive(Component)]
 #[require(SyncToRenderWorld, Transform, Visibility, VisibilityClass)]
 #[component(on_add = visibility::add_visibility_class::<LightOccluder2d>)]
 pub struct LightOccluder2d {
     /// The shape of the light occluder.
     pub shape: LightOccluder2dShape,
+    /// Makes the occluder glow, such as a neon wall or a lava rock. Light from outside the
+    /// occluder is still blocked by it.
+    ///
+    /// The occluder lights its surroundings with the given color, fading out by the given distance
+    /// from its surface. Colors brighter than white (such as `Color::linear_rgb(4.0, 1.0, 0.2)`)
+    /// can be used for a more intense glow. The glow only reaches places this occluder is the
+    /// nearest to, so other occluders nearby cut it off.
+    pub emission: Option<(Color, f32)>,
+    /// How far light reaches into the occluder, in world units.
+    ///
+    /// By default, the inside of an occluder only receives ambient light. With some penetration,
+    /// the edge of the occluder facing a light is lit too, fading out with depth. This suits
+    /// top-down walls, where the occluder covers the wall sprite itself.
+    pub light_penetration: f32,
+    /// How tall the occluder is, in world units.
+    ///
+    /// Occluders are infinitely tall by default, casting shadows that stretch to the edge of a
+    /// light's radius. A shorter occluder casts a shorter shadow, and lights higher than the
+    /// occluder (see [`PointLight2d::height`](crate::light::PointLight2d::height)) shine over it.
+    pub height: f32,
+    /// How much light the occluder reflects, from `0.0` (none) to `1.0` (a perfect mirror).
+    ///
+    /// Lights that cast shadows bounce off a reflective occluder's surface, lighting areas they
+    /// can't reach directly, such as around a corner. See
+    /// [`PointLight2d::max_reflections`](crate::light::PointLight2d::max_reflections), and
+    /// [`LightBeams2d`](crate::reflection::LightBeams2d) for tracing reflected beams in gameplay
+    /// code.
+    pub reflectivity: f32,
+    /// The color reflected light is tinted with.
+    pub reflection_tint: Color,
+}
+
+impl Default for LightOccluder2d {
+    fn default() -> Self {
+        Self {
+            shape: LightOccluder2dShape::default(),
+            emission: None,
+            light_penetration: 0.0,
+            height: f32::INFINITY,
+            reflectivity: 0.0,
+            reflection_tint: Color::WHITE,
+        }
+    }
 }
 
 /// Shape data for a light occluder.
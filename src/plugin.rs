@@ -5,21 +5,57 @@ use bevy::{
     core_pipeline::{Core2d, Core2dSystems},
     prelude::*,
     render::{
-        Render, RenderApp, RenderSystems, extract_component::UniformComponentPlugin,
+        Render, RenderApp, RenderSystems,
+        extract_component::UniformComponentPlugin,
         gpu_component_array_buffer::GpuComponentArrayBufferPlugin,
-        render_resource::SpecializedRenderPipelines, view::prepare_view_targets,
+        render_resource::{GpuArrayBuffer, SpecializedRenderPipelines},
+        renderer::RenderDevice,
+        view::prepare_view_targets,
     },
 };
 
 use crate::{
-    light::{AmbientLight2d, PointLight2d, SpotLight2d},
+    composite::{LightColorGrading2d, LightComposite2d},
+    day_night::{DayNightCycle2d, advance_day_night_cycle},
+    exposure::AutoExposure2d,
+    fog::{Fog2d, FogVolume2d},
+    global_illumination::GlobalIllumination2d,
+    lens_flare::LensFlare2d,
+    light::{
+        AmbientLight2d, AmbientZone2d, GlobalAmbientLight2d, Light2d, LightEmitterBatch2d,
+        LightHalo2d, PointLight2d, SpotLight2d,
+    },
+    modulation::{LightFlicker2d, LightModulation2d, LightPulse2d, modulate_lights},
     render::{
         TYPES_SHADER, VIEW_TRANSFORMATIONS_SHADER,
+        auto_exposure::{
+            AUTO_EXPOSURE_SHADER, AutoExposurePipeline, LUMINANCE_SHADER, auto_exposure_pass,
+            prepare_auto_exposure_textures,
+        },
         empty_buffer::{EmptyBuffer, prepare_empty_buffer},
         extract::{
-            ExtractedAmbientLight2d, ExtractedLightOccluder2d, ExtractedPointLight2d,
-            ExtractedSpotLight2d, extract_ambient_lights, extract_light_occluders,
-            extract_point_lights, extract_spot_lights,
+            ExtractedAmbientLight2d, ExtractedAutoExposure2d, ExtractedGlobalIllumination2d,
+            ExtractedLightColorGrading2d, ExtractedLightComposite2d, ExtractedLightInstance2d,
+            ExtractedLightInstances, ExtractedLightMapSettings, ExtractedLightOccluder2d,
+            ExtractedOccluderEmission2d, ExtractedOccluderEmissions, ExtractedPointLight2d,
+            ExtractedSpotLight2d, extract_ambient_lights, extract_auto_exposure,
+            extract_emissive_sprites, extract_fog_volumes, extract_global_illumination,
+            extract_layer_sprites, extract_lens_flares, extract_light_color_grading,
+            extract_light_composites, extract_light_emitter_batches, extract_light_map_settings,
+            extract_light_occluders, extract_point_lights, extract_reflected_lights,
+            extract_spot_lights, extract_transient_lights,
+        },
+        global_illumination::{
+            GLOBAL_ILLUMINATION_SHADER, GlobalIlluminationPipeline, global_illumination_pass,
+            prepare_global_illumination_texture,
+        },
+        lens_flare::{
+            ExtractedLensFlares, LENS_FLARE_SHADER, LensFlareMeta, LensFlarePipeline,
+            lens_flare_pass, prepare_lens_flare_pipelines, prepare_lens_flares,
+        },
+        light_instances::{
+            LIGHT_INSTANCES_SHADER, LightInstanceCount, LightInstancesPipeline,
+            light_instances_pass, prepare_light_instances, prepare_light_instances_texture,
         },
         light_map::{
             LIGHT_MAP_SHADER, LightMapPipeline, PointLightMetaBuffer, SpotLightMetaBuffer,
@@ -28,15 +64,42 @@ use crate::{
         },
         lighting::{LIGHTING_SHADER, LightingPipeline, lighting_pass, prepare_lighting_pipelines},
         sdf::{
-            OccluderMetaBuffer, SDF_SHADER, SdfPipeline, prepare_occluder_meta,
-            prepare_sdf_texture, sdf_pass,
+            OccluderEmissionMetaBuffer, OccluderMetaBuffer, SDF_SHADER, SdfPipeline,
+            prepare_occluder_emissions, prepare_occluder_meta, prepare_sdf_texture, sdf_pass,
+        },
+        sprite_layers::{
+            ExtractedSpriteLayers, SPRITE_LAYERS_SHADER, SpriteLayerMeta, SpriteLayersPipeline,
+            prepare_sprite_layer_pipelines, prepare_sprite_layer_textures, prepare_sprite_layers,
+            sprite_layers_pass,
         },
     },
+    sprite::{Emissive2d, NormalMap2d, Specular2d, Unlit2d},
+    transient::{TransientLight2d, tick_transient_lights},
 };
 
 /// A plugin that provides 2d lighting for an app.
+///
+/// # Render order
+///
+/// Lighting is rendered in the [`Core2d`] schedule, within [`Light2dSystems`]. These systems run
+/// after [`Core2dSystems::MainPass`], so everything drawn by the main pass is lit, and before
+/// [`Core2dSystems::EarlyPostProcess`], so all of Bevy's post processing sees the lit scene. This
+/// includes [`Bloom`](https://docs.rs/bevy/latest/bevy/post_process/bloom/struct.Bloom.html) and
+/// [`Tonemapping`](bevy::core_pipeline::tonemapping::Tonemapping), which run in
+/// [`Core2dSystems::PostProcess`].
+///
+/// # HDR
+///
+/// The light map is stored as floating point, so light can be brighter than `1.0`. On a camera
+/// with [`Hdr`](bevy::camera::Hdr), the lit scene keeps these values, so strong lights are
+/// compressed by tonemapping and picked up by bloom. Without it, they're clamped to `1.0`.
 pub struct Light2dPlugin;
 
+/// The system set in the [`Core2d`] schedule which renders 2d lighting. See [`Light2dPlugin`] for
+/// how it's ordered.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Light2dSystems;
+
 impl Plugin for Light2dPlugin {
     fn build(&self, app: &mut App) {
         load_internal_asset!(app, TYPES_SHADER, "render/types.wgsl", Shader::from_wgsl);
@@ -59,16 +122,88 @@ impl Plugin for Light2dPlugin {
             "render/light_map/light_map.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            LIGHT_INSTANCES_SHADER,
+            "render/light_instances/light_instances.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            SPRITE_LAYERS_SHADER,
+            "render/sprite_layers/sprite_layers.wgsl",
+            Shader::from_wgsl
+        );
+
+        load_internal_asset!(
+            app,
+            GLOBAL_ILLUMINATION_SHADER,
+            "render/global_illumination/global_illumination.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            AUTO_EXPOSURE_SHADER,
+            "render/auto_exposure/auto_exposure.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            LUMINANCE_SHADER,
+            "render/auto_exposure/luminance.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            LENS_FLARE_SHADER,
+            "render/lens_flare/lens_flare.wgsl",
+            Shader::from_wgsl
+        );
 
         app.add_plugins((
             UniformComponentPlugin::<ExtractedAmbientLight2d>::default(),
+            UniformComponentPlugin::<ExtractedLightMapSettings>::default(),
+            UniformComponentPlugin::<ExtractedGlobalIllumination2d>::default(),
+            UniformComponentPlugin::<ExtractedAutoExposure2d>::default(),
+            UniformComponentPlugin::<ExtractedLightComposite2d>::default(),
+            UniformComponentPlugin::<ExtractedLightColorGrading2d>::default(),
             GpuComponentArrayBufferPlugin::<ExtractedPointLight2d>::default(),
             GpuComponentArrayBufferPlugin::<ExtractedLightOccluder2d>::default(),
             GpuComponentArrayBufferPlugin::<ExtractedSpotLight2d>::default(),
         ))
+        .register_type::<Light2d>()
         .register_type::<AmbientLight2d>()
+        .register_type::<GlobalAmbientLight2d>()
+        .register_type::<AmbientZone2d>()
+        .init_resource::<GlobalAmbientLight2d>()
+        .register_type::<LightHalo2d>()
         .register_type::<PointLight2d>()
-        .register_type::<SpotLight2d>();
+        .register_type::<SpotLight2d>()
+        .register_type::<LightEmitterBatch2d>()
+        .register_type::<LightFlicker2d>()
+        .register_type::<LightPulse2d>()
+        .register_type::<LightModulation2d>()
+        .register_type::<TransientLight2d>()
+        .register_type::<Emissive2d>()
+        .register_type::<Unlit2d>()
+        .register_type::<NormalMap2d>()
+        .register_type::<Specular2d>()
+        .register_type::<Fog2d>()
+        .register_type::<FogVolume2d>()
+        .register_type::<LensFlare2d>()
+        .register_type::<GlobalIllumination2d>()
+        .register_type::<DayNightCycle2d>()
+        .register_type::<AutoExposure2d>()
+        .register_type::<LightComposite2d>()
+        .register_type::<LightColorGrading2d>()
+        .add_systems(
+            PostUpdate,
+            (
+                modulate_lights,
+                tick_transient_lights,
+                advance_day_night_cycle.run_if(resource_exists::<DayNightCycle2d>),
+            ),
+        );
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
@@ -76,17 +211,41 @@ impl Plugin for Light2dPlugin {
 
         render_app
             .init_resource::<SpecializedRenderPipelines<LightingPipeline>>()
+            .init_resource::<SpecializedRenderPipelines<SpriteLayersPipeline>>()
+            .init_resource::<ExtractedSpriteLayers>()
+            .init_resource::<SpriteLayerMeta>()
+            .init_resource::<SpecializedRenderPipelines<LensFlarePipeline>>()
+            .init_resource::<ExtractedLensFlares>()
+            .init_resource::<LensFlareMeta>()
             .init_resource::<PointLightMetaBuffer>()
             .init_resource::<SpotLightMetaBuffer>()
+            .init_resource::<LightInstanceCount>()
+            .init_resource::<ExtractedLightInstances>()
+            .init_resource::<OccluderEmissionMetaBuffer>()
+            .init_resource::<ExtractedOccluderEmissions>()
             .init_resource::<OccluderMetaBuffer>()
             .init_resource::<EmptyBuffer>()
             .add_systems(
                 ExtractSchedule,
                 (
                     extract_point_lights,
+                    extract_reflected_lights,
                     extract_light_occluders,
                     extract_ambient_lights,
                     extract_spot_lights,
+                    extract_transient_lights,
+                    extract_light_emitter_batches,
+                    extract_light_map_settings,
+                    extract_emissive_sprites,
+                    extract_layer_sprites::<Unlit2d>,
+                    extract_layer_sprites::<NormalMap2d>,
+                    extract_layer_sprites::<Specular2d>,
+                    extract_fog_volumes,
+                    extract_lens_flares,
+                    extract_global_illumination,
+                    extract_auto_exposure,
+                    extract_light_composites,
+                    extract_light_color_grading,
                 ),
             )
             .add_systems(
@@ -95,6 +254,12 @@ impl Plugin for Light2dPlugin {
                     prepare_lighting_pipelines.in_set(RenderSystems::Prepare),
                     prepare_point_light_count.in_set(RenderSystems::Prepare),
                     prepare_spot_light_count.in_set(RenderSystems::Prepare),
+                    prepare_light_instances.in_set(RenderSystems::Prepare),
+                    prepare_occluder_emissions.in_set(RenderSystems::Prepare),
+                    prepare_sprite_layer_pipelines.in_set(RenderSystems::Prepare),
+                    prepare_sprite_layers.in_set(RenderSystems::Prepare),
+                    prepare_lens_flare_pipelines.in_set(RenderSystems::Prepare),
+                    prepare_lens_flares.in_set(RenderSystems::Prepare),
                     prepare_occluder_meta.in_set(RenderSystems::Prepare),
                     prepare_empty_buffer.in_set(RenderSystems::Prepare),
                     prepare_sdf_texture
@@ -103,12 +268,38 @@ impl Plugin for Light2dPlugin {
                     prepare_light_map_texture
                         .after(prepare_view_targets)
                         .in_set(RenderSystems::PrepareViews),
+                    prepare_light_instances_texture
+                        .after(prepare_view_targets)
+                        .in_set(RenderSystems::PrepareViews),
+                    prepare_sprite_layer_textures
+                        .after(prepare_view_targets)
+                        .in_set(RenderSystems::PrepareViews),
+                    prepare_global_illumination_texture
+                        .after(prepare_view_targets)
+                        .in_set(RenderSystems::PrepareViews),
+                    prepare_auto_exposure_textures
+                        .after(prepare_view_targets)
+                        .in_set(RenderSystems::PrepareViews),
                 ),
             )
             .add_systems(
                 Core2d,
-                (sdf_pass, light_map_pass, lighting_pass)
+                (
+                    sdf_pass,
+                    sprite_layers_pass,
+                    light_instances_pass,
+                    light_map_pass,
+                    global_illumination_pass,
+                    auto_exposure_pass,
+                    lighting_pass,
+                    lens_flare_pass,
+                )
                     .chain()
+                    .in_set(Light2dSystems),
+            )
+            .configure_sets(
+                Core2d,
+                Light2dSystems
                     .after(Core2dSystems::MainPass)
                     .before(Core2dSystems::EarlyPostProcess),
             );
@@ -119,9 +310,18 @@ impl Plugin for Light2dPlugin {
             return;
         };
 
+        let limits = render_app.world().resource::<RenderDevice>().limits();
+
         render_app
+            .insert_resource(GpuArrayBuffer::<ExtractedLightInstance2d>::new(&limits))
+            .insert_resource(GpuArrayBuffer::<ExtractedOccluderEmission2d>::new(&limits))
             .init_resource::<LightingPipeline>()
             .init_resource::<SdfPipeline>()
+            .init_resource::<SpriteLayersPipeline>()
+            .init_resource::<LensFlarePipeline>()
+            .init_resource::<LightInstancesPipeline>()
+            .init_resource::<GlobalIlluminationPipeline>()
+            .init_resource::<AutoExposurePipeline>()
             .init_resource::<LightMapPipeline>();
     }
 }
@@ -0,0 +1,486 @@
+//! A module which contains reflections off occluders, and tracing reflected light beams.
+
+use bevy::{
+    camera::visibility::InheritedVisibility,
+    color::{Color, ColorToComponents, LinearRgba},
+    ecs::{
+        entity::Entity,
+        system::{Query, SystemParam},
+    },
+    math::{Dir2, Vec2, Vec3},
+    transform::components::GlobalTransform,
+};
+
+use crate::occluder::{LightOccluder2d, LightOccluder2dShape};
+
+/// The most mirror images rendered for a single light, so a hall of mirrors can't fill up the
+/// light buffers.
+const MAX_MIRROR_IMAGES: usize = 8;
+
+/// How far a reflected beam is moved away from the surface it bounced off, so it doesn't hit the
+/// same surface again.
+const SURFACE_OFFSET: f32 = 1e-3;
+
+/// The path taken by a light beam, as traced by [`LightBeams2d`].
+#[derive(Clone, Debug)]
+pub struct LightBeamPath2d {
+    /// The points along the beam, starting at its origin.
+    ///
+    /// Each point after the first is where the beam reflected off an occluder, apart from the
+    /// last, which is where the beam ends.
+    pub points: Vec<Vec2>,
+    /// The occluders the beam reflected off, in order.
+    pub reflected_by: Vec<Entity>,
+    /// The occluder the beam ended on, or `None` if it travelled its full distance.
+    pub blocked_by: Option<Entity>,
+    /// The beam's color by the end of its path, after being tinted by each reflection.
+    pub tint: Color,
+}
+
+impl LightBeamPath2d {
+    /// The total length of the beam, in world units.
+    pub fn length(&self) -> f32 {
+        self.points
+            .windows(2)
+            .map(|segment| segment[0].distance(segment[1]))
+            .sum()
+    }
+
+    /// Where the beam ends.
+    pub fn end(&self) -> Vec2 {
+        self.points.last().copied().unwrap_or_default()
+    }
+}
+
+/// A system parameter for tracing light beams through the scene's occluders, reflecting off them
+/// the same way lights do (see [`LightOccluder2d::reflectivity`]).
+///
+/// This is useful for gameplay built around light, such as puzzles where beams are redirected by
+/// mirrors onto targets.
+#[derive(SystemParam)]
+pub struct LightBeams2d<'w, 's> {
+    occluders: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static LightOccluder2d,
+            &'static GlobalTransform,
+            &'static InheritedVisibility,
+        ),
+    >,
+}
+
+impl LightBeams2d<'_, '_> {
+    /// Traces a beam from `origin` in the given `direction`, for up to `max_distance` world units.
+    ///
+    /// The beam reflects off up to `max_reflections` reflective occluders, and ends at the first
+    /// occluder it can't reflect off.
+    pub fn trace(
+        &self,
+        origin: Vec2,
+        direction: Dir2,
+        max_distance: f32,
+        max_reflections: u32,
+    ) -> LightBeamPath2d {
+        let occluders: Vec<_> = self
+            .occluders
+            .iter()
+            .filter(|(.., inherited_visibility)| inherited_visibility.get())
+            .map(|(entity, occluder, global_transform, _)| {
+                OccluderBox::new(entity, occluder, global_transform)
+            })
+            .collect();
+
+        trace_beam(
+            &occluders,
+            origin,
+            *direction,
+            max_distance,
+            max_reflections,
+        )
+    }
+}
+
+/// An occluder's shape in world space, along with how it reflects light.
+pub(crate) struct OccluderBox {
+    pub entity: Entity,
+    pub center: Vec2,
+    pub half_size: Vec2,
+    /// The reflection tint, scaled by the reflectivity.
+    pub reflection: Vec3,
+}
+
+impl OccluderBox {
+    pub fn new(
+        entity: Entity,
+        occluder: &LightOccluder2d,
+        global_transform: &GlobalTransform,
+    ) -> Self {
+        let half_size = match occluder.shape {
+            LightOccluder2dShape::Rectangle { half_size } => half_size,
+        };
+
+        Self {
+            entity,
+            center: global_transform.translation().truncate(),
+            half_size,
+            reflection: occluder.reflection_tint.to_linear().to_vec3()
+                * occluder.reflectivity.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn is_reflective(&self) -> bool {
+        self.reflection.max_element() > 0.0
+    }
+
+    /// Returns the distance along the ray to the box, and the normal of the side it hits.
+    fn intersect(&self, origin: Vec2, direction: Vec2) -> Option<(f32, Vec2)> {
+        let mut enter = (f32::NEG_INFINITY, Vec2::ZERO);
+        let mut exit = f32::INFINITY;
+
+        for axis in 0..2 {
+            let min = self.center[axis] - self.half_size[axis];
+            let max = self.center[axis] + self.half_size[axis];
+
+            if direction[axis] == 0.0 {
+                // Parallel to this pair of sides, so the ray is either always between them or
+                // never.
+                if origin[axis] < min || origin[axis] > max {
+                    return None;
+                }
+                continue;
+            }
+
+            let (near, far) = if direction[axis] > 0.0 {
+                (min, max)
+            } else {
+                (max, min)
+            };
+            let t_near = (near - origin[axis]) / direction[axis];
+            let t_far = (far - origin[axis]) / direction[axis];
+
+            if t_near > enter.0 {
+                let mut normal = Vec2::ZERO;
+                normal[axis] = -direction[axis].signum();
+                enter = (t_near, normal);
+            }
+            exit = exit.min(t_far);
+        }
+
+        if enter.0 > exit || exit < 0.0 {
+            return None;
+        }
+
+        // A ray starting inside the box hits it straight away, without hitting a side.
+        if enter.0 < 0.0 {
+            return Some((0.0, Vec2::ZERO));
+        }
+
+        Some(enter)
+    }
+}
+
+fn trace_beam(
+    occluders: &[OccluderBox],
+    origin: Vec2,
+    direction: Vec2,
+    max_distance: f32,
+    max_reflections: u32,
+) -> LightBeamPath2d {
+    let mut path = LightBeamPath2d {
+        points: vec![origin],
+        reflected_by: Vec::new(),
+        blocked_by: None,
+        tint: Color::WHITE,
+    };
+
+    let mut origin = origin;
+    let mut direction = direction.normalize_or_zero();
+    let mut remaining = max_distance.max(0.0);
+    let mut tint = Vec3::ONE;
+
+    loop {
+        let hit = occluders
+            .iter()
+            .filter_map(|occluder| {
+                let (distance, normal) = occluder.intersect(origin, direction)?;
+                Some((distance, normal, occluder))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+
+        let Some((distance, normal, occluder)) = hit.filter(|(distance, ..)| *distance < remaining)
+        else {
+            path.points.push(origin + direction * remaining);
+            break;
+        };
+
+        origin += direction * distance;
+        remaining -= distance;
+        path.points.push(origin);
+
+        // Inside an occluder, there's no surface to reflect off.
+        let can_reflect = occluder.is_reflective()
+            && normal != Vec2::ZERO
+            && path.reflected_by.len() < max_reflections as usize;
+
+        if !can_reflect {
+            path.blocked_by = Some(occluder.entity);
+            break;
+        }
+
+        direction = direction.reflect(normal);
+        origin += direction * SURFACE_OFFSET;
+        tint *= occluder.reflection;
+        path.reflected_by.push(occluder.entity);
+    }
+
+    path.tint = LinearRgba::from_vec3(tint).into();
+    path
+}
+
+/// A light reflected in one or more occluders, seen from the other side of them.
+#[derive(Clone, Copy)]
+pub(crate) struct MirrorImage {
+    pub position: Vec2,
+    /// Multiplies the light's direction, flipping it for each reflection.
+    pub flip: Vec2,
+    pub tint: Vec3,
+    pub reflections: u32,
+}
+
+/// Finds the mirror images of a light in the sides of reflective occluders it reaches.
+///
+/// Each image lights the scene as if the light were on the far side of the mirror. The light map
+/// only keeps the light where a ray towards the image bounces off the expected number of
+/// mirrors, which limits it to the area actually reached by the reflection.
+pub(crate) fn mirror_images(
+    occluders: &[OccluderBox],
+    position: Vec2,
+    radius: f32,
+    max_reflections: u32,
+) -> Vec<MirrorImage> {
+    let mut images = Vec::new();
+    let mut sources = vec![(
+        MirrorImage {
+            position,
+            flip: Vec2::ONE,
+            tint: Vec3::ONE,
+            reflections: 0,
+        },
+        None,
+    )];
+
+    for _ in 0..max_reflections {
+        let mut next_sources = Vec::new();
+
+        for (source, last_occluder) in &sources {
+            for (index, occluder) in occluders.iter().enumerate() {
+                // Light reflected off a box can't reach any side of that same box.
+                if !occluder.is_reflective() || *last_occluder == Some(index) {
+                    continue;
+                }
+
+                for (axis, sign) in [(0, 1.0), (0, -1.0), (1, 1.0), (1, -1.0)] {
+                    let side = occluder.center[axis] + occluder.half_size[axis] * sign;
+
+                    // Only the sides facing the light can reflect it.
+                    if (source.position[axis] - side) * sign <= 0.0 {
+                        continue;
+                    }
+
+                    let other = 1 - axis;
+                    let mut closest = Vec2::ZERO;
+                    closest[axis] = side;
+                    closest[other] = source.position[other].clamp(
+                        occluder.center[other] - occluder.half_size[other],
+                        occluder.center[other] + occluder.half_size[other],
+                    );
+                    if closest.distance(source.position) >= radius {
+                        continue;
+                    }
+
+                    let mut image_position = source.position;
+                    image_position[axis] = 2.0 * side - source.position[axis];
+                    let mut flip = source.flip;
+                    flip[axis] = -flip[axis];
+
+                    next_sources.push((
+                        MirrorImage {
+                            position: image_position,
+                            flip,
+                            tint: source.tint * occluder.reflection,
+                            reflections: source.reflections + 1,
+                        },
+                        Some(index),
+                    ));
+                }
+            }
+        }
+
+        images.extend(next_sources.iter().map(|(image, _)| *image));
+        if images.len() >= MAX_MIRROR_IMAGES {
+            images.truncate(MAX_MIRROR_IMAGES);
+            break;
+        }
+
+        sources = next_sources;
+    }
+
+    images
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn occluder(index: u32, center: Vec2, half_size: Vec2, reflection: Vec3) -> OccluderBox {
+        OccluderBox {
+            entity: Entity::from_raw_u32(index).unwrap(),
+            center,
+            half_size,
+            reflection,
+        }
+    }
+
+    fn assert_points_near(actual: &[Vec2], expected: &[Vec2]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?}");
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                actual.abs_diff_eq(*expected, 1e-2),
+                "{actual} != {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn ray_hits_nearest_side() {
+        let wall = occluder(1, Vec2::ZERO, Vec2::ONE, Vec3::ZERO);
+
+        assert_eq!(
+            wall.intersect(Vec2::new(-5.0, 0.0), Vec2::X),
+            Some((4.0, Vec2::NEG_X))
+        );
+        assert_eq!(
+            wall.intersect(Vec2::new(0.0, 5.0), Vec2::NEG_Y),
+            Some((4.0, Vec2::Y))
+        );
+        assert_eq!(wall.intersect(Vec2::new(-5.0, 3.0), Vec2::X), None);
+        assert_eq!(wall.intersect(Vec2::new(-5.0, 0.0), Vec2::NEG_X), None);
+    }
+
+    #[test]
+    fn ray_starting_inside_hits_straight_away() {
+        let wall = occluder(1, Vec2::ZERO, Vec2::ONE, Vec3::ONE);
+
+        assert_eq!(
+            wall.intersect(Vec2::new(0.5, 0.0), Vec2::X),
+            Some((0.0, Vec2::ZERO))
+        );
+
+        // Even a mirror can't reflect a beam from inside it.
+        let path = trace_beam(&[wall], Vec2::new(0.5, 0.0), Vec2::X, 10.0, 4);
+        assert_eq!(path.blocked_by, Some(Entity::from_raw_u32(1).unwrap()));
+        assert!(path.reflected_by.is_empty());
+        assert_eq!(path.length(), 0.0);
+    }
+
+    #[test]
+    fn beam_bounces_off_two_mirrors_onto_a_wall() {
+        let occluders = [
+            occluder(
+                1,
+                Vec2::new(10.0, 10.0),
+                Vec2::new(1.0, 20.0),
+                Vec3::new(1.0, 0.5, 1.0),
+            ),
+            occluder(
+                2,
+                Vec2::new(-5.0, 20.0),
+                Vec2::new(10.0, 1.0),
+                Vec3::new(1.0, 1.0, 0.5),
+            ),
+            occluder(3, Vec2::new(-12.0, 5.0), Vec2::new(2.0, 3.0), Vec3::ZERO),
+        ];
+
+        let path = trace_beam(&occluders, Vec2::ZERO, Vec2::ONE, 100.0, 4);
+
+        assert_points_near(
+            &path.points,
+            &[
+                Vec2::ZERO,
+                Vec2::new(9.0, 9.0),
+                Vec2::new(-1.0, 19.0),
+                Vec2::new(-12.0, 8.0),
+            ],
+        );
+        assert_eq!(
+            path.reflected_by,
+            [
+                Entity::from_raw_u32(1).unwrap(),
+                Entity::from_raw_u32(2).unwrap()
+            ]
+        );
+        assert_eq!(path.blocked_by, Some(Entity::from_raw_u32(3).unwrap()));
+
+        let tint = path.tint.to_linear().to_vec3();
+        assert!(tint.abs_diff_eq(Vec3::new(1.0, 0.5, 0.5), 1e-5), "{tint}");
+
+        // Out of reflections, the second mirror blocks the beam instead.
+        let path = trace_beam(&occluders, Vec2::ZERO, Vec2::ONE, 100.0, 1);
+        assert_eq!(path.reflected_by, [Entity::from_raw_u32(1).unwrap()]);
+        assert_eq!(path.blocked_by, Some(Entity::from_raw_u32(2).unwrap()));
+    }
+
+    #[test]
+    fn beam_stops_at_max_distance() {
+        let path = trace_beam(&[], Vec2::ZERO, Vec2::new(3.0, 4.0), 10.0, 4);
+
+        assert_points_near(&path.points, &[Vec2::ZERO, Vec2::new(6.0, 8.0)]);
+        assert_eq!(path.blocked_by, None);
+    }
+
+    #[test]
+    fn light_is_mirrored_in_the_facing_side() {
+        let mirror = occluder(1, Vec2::new(5.0, 0.0), Vec2::ONE, Vec3::ONE);
+
+        let images = mirror_images(&[mirror], Vec2::ZERO, 10.0, 1);
+
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].position, Vec2::new(8.0, 0.0));
+        assert_eq!(images[0].flip, Vec2::new(-1.0, 1.0));
+        assert_eq!(images[0].reflections, 1);
+    }
+
+    #[test]
+    fn light_is_not_mirrored_out_of_reach() {
+        let mirror = occluder(1, Vec2::new(5.0, 0.0), Vec2::ONE, Vec3::ONE);
+        let wall = occluder(2, Vec2::new(5.0, 0.0), Vec2::ONE, Vec3::ZERO);
+
+        assert!(mirror_images(&[mirror], Vec2::ZERO, 3.0, 1).is_empty());
+        assert!(mirror_images(&[wall], Vec2::ZERO, 10.0, 1).is_empty());
+    }
+
+    #[test]
+    fn facing_mirrors_reflect_back_and_forth() {
+        let mirrors = [
+            occluder(1, Vec2::new(5.0, 0.0), Vec2::ONE, Vec3::ONE),
+            occluder(2, Vec2::new(-5.0, 0.0), Vec2::ONE, Vec3::ONE),
+        ];
+
+        let images = mirror_images(&mirrors, Vec2::ZERO, 100.0, 2);
+
+        // One image in each mirror, then each of those reflected in the other mirror.
+        let positions: Vec<_> = images.iter().map(|image| image.position).collect();
+        assert_eq!(
+            positions,
+            [
+                Vec2::new(8.0, 0.0),
+                Vec2::new(-8.0, 0.0),
+                Vec2::new(-16.0, 0.0),
+                Vec2::new(16.0, 0.0),
+            ]
+        );
+        assert!(images[2..].iter().all(|image| image.reflections == 2));
+    }
+}
@@ -0,0 +1,32 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_light_2d::types::AutoExposure2d
+
+@group(0) @binding(0)
+var<uniform> settings: AutoExposure2d;
+
+// The light map's average log luminance, in the red channel of a single texel.
+@group(0) @binding(1)
+var average_luminance: texture_2d<f32>;
+
+// Last frame's exposure, or zero if there isn't one yet.
+@group(0) @binding(2)
+var previous_exposure: texture_2d<f32>;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let average = exp2(textureLoad(average_luminance, vec2(0), 0).r);
+
+    // The exposure that brings the average luminance back to one.
+    let target_exposure = clamp(1.0 / average, settings.min_exposure, settings.max_exposure);
+
+    let previous = textureLoad(previous_exposure, vec2(0), 0).r;
+    if previous <= 0.0 {
+        return vec4(target_exposure, 0.0, 0.0, 1.0);
+    }
+
+    let speed = select(settings.darken_speed, settings.brighten_speed, target_exposure > previous);
+    let blend = 1.0 - exp(-settings.delta_time * speed);
+    let exposure = exp2(mix(log2(previous), log2(target_exposure), blend));
+
+    return vec4(exposure, 0.0, 0.0, 1.0);
+}
@@ -0,0 +1,46 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+// The size of the largest mip level. Keep this in sync with `prepare.rs`.
+const LUMINANCE_SIZE: f32 = 256.0;
+
+// Each texel of the largest mip level averages a grid of this many bilinear samples in each
+// direction, so light maps up to eight times larger are covered without gaps.
+const SAMPLES: u32 = 4u;
+
+// Keeps black areas from pulling the average luminance down to zero.
+const MIN_LUMINANCE: f32 = 1e-3;
+
+// The light map, or the mip level above the one being written.
+@group(0) @binding(0)
+var source_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+// Writes the light map's log luminance into the largest mip level. The average is taken in log
+// space, so a few bright lights don't dominate.
+@fragment
+fn luminance(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    var total = 0.0;
+    for (var y = 0u; y < SAMPLES; y++) {
+        for (var x = 0u; x < SAMPLES; x++) {
+            let offset = (vec2(f32(x), f32(y)) + 0.5) / f32(SAMPLES) - 0.5;
+            let uv = in.uv + offset / LUMINANCE_SIZE;
+            let light = textureSampleLevel(source_texture, source_sampler, uv, 0.0).rgb;
+            total += log2(max(luminance_of(light), MIN_LUMINANCE));
+        }
+    }
+
+    return vec4(total / f32(SAMPLES * SAMPLES), 0.0, 0.0, 1.0);
+}
+
+// Averages the mip level above. Each texel's center lies between four texels of the level above,
+// so a single bilinear sample averages all of them.
+@fragment
+fn downsample(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    return textureSampleLevel(source_texture, source_sampler, in.uv, 0.0);
+}
+
+fn luminance_of(color: vec3<f32>) -> f32 {
+    return dot(color, vec3(0.2126, 0.7152, 0.0722));
+}
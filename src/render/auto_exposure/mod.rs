@@ -0,0 +1,43 @@
+mod node;
+mod pipeline;
+mod prepare;
+
+use bevy::{
+    asset::{Handle, weak_handle},
+    ecs::component::Component,
+    render::render_resource::TextureView,
+    shader::Shader,
+};
+
+pub use node::auto_exposure_pass;
+pub use pipeline::AutoExposurePipeline;
+pub use prepare::prepare_auto_exposure_textures;
+
+pub const AUTO_EXPOSURE_SHADER: Handle<Shader> =
+    weak_handle!("5c71d0e3-92a4-4b8f-a6d1-0e3f7b2c9d84");
+pub const LUMINANCE_SHADER: Handle<Shader> = weak_handle!("372420d1-cb47-41a0-8f66-8e1e31bbe587");
+
+/// A view's exposure, in the red channel of a single texel.
+///
+/// The exposure is smoothed over time, so each frame reads the last frame's exposure from one
+/// texture and writes to the other. Unlike most view textures, these are kept between frames.
+#[derive(Component)]
+pub struct AutoExposureTextures {
+    pub textures: [TextureView; 2],
+    pub current: usize,
+    /// The light map's log luminance, with a view of each mip level. Each level averages the one
+    /// above it, down to a single texel holding the average of the whole light map.
+    pub luminance: Vec<TextureView>,
+}
+
+impl AutoExposureTextures {
+    /// The texture written to this frame.
+    pub fn current(&self) -> &TextureView {
+        &self.textures[self.current]
+    }
+
+    /// The texture written to last frame.
+    pub fn previous(&self) -> &TextureView {
+        &self.textures[1 - self.current]
+    }
+}
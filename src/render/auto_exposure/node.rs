@@ -0,0 +1,119 @@
+use std::iter;
+
+use bevy::prelude::*;
+use bevy::render::extract_component::{ComponentUniforms, DynamicUniformIndex};
+use bevy::render::render_resource::{
+    BindGroupEntries, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+};
+use bevy::render::renderer::{RenderContext, ViewQuery};
+
+use crate::render::extract::ExtractedAutoExposure2d;
+use crate::render::light_map::LightMapTexture;
+
+use super::{AutoExposurePipeline, AutoExposureTextures};
+
+const AUTO_EXPOSURE_PASS: &str = "auto_exposure_pass";
+const AUTO_EXPOSURE_BIND_GROUP: &str = "auto_exposure_bind_group";
+const LUMINANCE_PASS: &str = "auto_exposure_luminance_pass";
+const LUMINANCE_BIND_GROUP: &str = "auto_exposure_luminance_bind_group";
+
+pub fn auto_exposure_pass(
+    world: &World,
+    view: ViewQuery<(
+        &DynamicUniformIndex<ExtractedAutoExposure2d>,
+        &AutoExposureTextures,
+        &LightMapTexture,
+    )>,
+    mut ctx: RenderContext,
+) {
+    let (settings_index, auto_exposure_textures, light_map_texture) = view.into_inner();
+
+    let auto_exposure_pipeline = world.resource::<AutoExposurePipeline>();
+    let pipeline_cache = world.resource::<PipelineCache>();
+
+    let (
+        Some(luminance_pipeline),
+        Some(downsample_pipeline),
+        Some(pipeline),
+        Some(settings_binding),
+        Some(average_luminance),
+    ) = (
+        pipeline_cache.get_render_pipeline(auto_exposure_pipeline.luminance_pipeline_id),
+        pipeline_cache.get_render_pipeline(auto_exposure_pipeline.downsample_pipeline_id),
+        pipeline_cache.get_render_pipeline(auto_exposure_pipeline.pipeline_id),
+        world
+            .resource::<ComponentUniforms<ExtractedAutoExposure2d>>()
+            .uniforms()
+            .binding(),
+        auto_exposure_textures.luminance.last(),
+    )
+    else {
+        return;
+    };
+
+    // Each mip level is read from the one above it, and the largest from the light map.
+    let luminance_layout =
+        pipeline_cache.get_bind_group_layout(&auto_exposure_pipeline.luminance_layout_descriptor);
+    let luminance_bind_groups: Vec<_> = iter::once(&light_map_texture.light_map.default_view)
+        .chain(&auto_exposure_textures.luminance)
+        .take(auto_exposure_textures.luminance.len())
+        .map(|source| {
+            ctx.render_device().create_bind_group(
+                LUMINANCE_BIND_GROUP,
+                &luminance_layout,
+                &BindGroupEntries::sequential((source, &auto_exposure_pipeline.luminance_sampler)),
+            )
+        })
+        .collect();
+
+    let bind_group = ctx.render_device().create_bind_group(
+        AUTO_EXPOSURE_BIND_GROUP,
+        &pipeline_cache.get_bind_group_layout(&auto_exposure_pipeline.layout_descriptor),
+        &BindGroupEntries::sequential((
+            settings_binding,
+            average_luminance,
+            auto_exposure_textures.previous(),
+        )),
+    );
+
+    for (level, (target, luminance_bind_group)) in auto_exposure_textures
+        .luminance
+        .iter()
+        .zip(&luminance_bind_groups)
+        .enumerate()
+    {
+        let mut luminance_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some(LUMINANCE_PASS),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: Operations::default(),
+                depth_slice: None,
+            })],
+            ..default()
+        });
+
+        luminance_pass.set_render_pipeline(if level == 0 {
+            luminance_pipeline
+        } else {
+            downsample_pipeline
+        });
+        luminance_pass.set_bind_group(0, luminance_bind_group, &[]);
+        luminance_pass.draw(0..3, 0..1);
+    }
+
+    let mut auto_exposure_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some(AUTO_EXPOSURE_PASS),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: auto_exposure_textures.current(),
+            resolve_target: None,
+            ops: Operations::default(),
+            depth_slice: None,
+        })],
+        ..default()
+    });
+
+    auto_exposure_pass.set_render_pipeline(pipeline);
+    auto_exposure_pass.set_bind_group(0, &bind_group, &[settings_index.index()]);
+    auto_exposure_pass.draw(0..3, 0..1);
+}
@@ -0,0 +1,132 @@
+use bevy::core_pipeline::FullscreenShader;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::world::{FromWorld, World};
+use bevy::prelude::default;
+use bevy::render::render_resource::binding_types::{sampler, texture_2d, uniform_buffer};
+use bevy::render::render_resource::{
+    BindGroupLayoutDescriptor, BindGroupLayoutEntries, CachedRenderPipelineId, ColorTargetState,
+    ColorWrites, FilterMode, FragmentState, MultisampleState, PipelineCache, PrimitiveState,
+    RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages,
+    TextureFormat, TextureSampleType,
+};
+use bevy::render::renderer::RenderDevice;
+
+use crate::render::extract::ExtractedAutoExposure2d;
+
+use super::{AUTO_EXPOSURE_SHADER, LUMINANCE_SHADER};
+
+const AUTO_EXPOSURE_PIPELINE: &str = "auto_exposure_pipeline";
+const AUTO_EXPOSURE_BIND_GROUP_LAYOUT: &str = "auto_exposure_bind_group_layout";
+const LUMINANCE_PIPELINE: &str = "auto_exposure_luminance_pipeline";
+const DOWNSAMPLE_PIPELINE: &str = "auto_exposure_downsample_pipeline";
+const LUMINANCE_BIND_GROUP_LAYOUT: &str = "auto_exposure_luminance_bind_group_layout";
+
+#[derive(Resource)]
+pub struct AutoExposurePipeline {
+    pub layout_descriptor: BindGroupLayoutDescriptor,
+    pub pipeline_id: CachedRenderPipelineId,
+    pub luminance_layout_descriptor: BindGroupLayoutDescriptor,
+    pub luminance_sampler: Sampler,
+    pub luminance_pipeline_id: CachedRenderPipelineId,
+    pub downsample_pipeline_id: CachedRenderPipelineId,
+}
+
+impl FromWorld for AutoExposurePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let luminance_layout_descriptor = BindGroupLayoutDescriptor::new(
+            LUMINANCE_BIND_GROUP_LAYOUT,
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
+        let luminance_sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        let layout_descriptor = BindGroupLayoutDescriptor::new(
+            AUTO_EXPOSURE_BIND_GROUP_LAYOUT,
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    uniform_buffer::<ExtractedAutoExposure2d>(true),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                ),
+            ),
+        );
+
+        let fullscreen_shader = world.resource::<FullscreenShader>().clone();
+        let pipeline_id =
+            world
+                .resource_mut::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some(AUTO_EXPOSURE_PIPELINE.into()),
+                    layout: vec![layout_descriptor.clone()],
+                    vertex: fullscreen_shader.to_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader: AUTO_EXPOSURE_SHADER,
+                        shader_defs: vec![],
+                        entry_point: Some("fragment".into()),
+                        targets: vec![Some(ColorTargetState {
+                            format: TextureFormat::Rgba16Float,
+                            blend: None,
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    immediate_size: 0,
+                    zero_initialize_workgroup_memory: false,
+                });
+
+        // The largest mip level is measured from the light map, and each smaller one averages
+        // the level above it.
+        let [luminance_pipeline_id, downsample_pipeline_id] = [
+            (LUMINANCE_PIPELINE, "luminance"),
+            (DOWNSAMPLE_PIPELINE, "downsample"),
+        ]
+        .map(|(label, entry_point)| {
+            world
+                .resource_mut::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some(label.into()),
+                    layout: vec![luminance_layout_descriptor.clone()],
+                    vertex: fullscreen_shader.to_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader: LUMINANCE_SHADER,
+                        shader_defs: vec![],
+                        entry_point: Some(entry_point.into()),
+                        targets: vec![Some(ColorTargetState {
+                            format: TextureFormat::Rgba16Float,
+                            blend: None,
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    immediate_size: 0,
+                    zero_initialize_workgroup_memory: false,
+                })
+        });
+
+        Self {
+            layout_descriptor,
+            pipeline_id,
+            luminance_layout_descriptor,
+            luminance_sampler,
+            luminance_pipeline_id,
+            downsample_pipeline_id,
+        }
+    }
+}
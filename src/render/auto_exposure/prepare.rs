@@ -0,0 +1,107 @@
+use bevy::{
+    ecs::{
+        entity::Entity,
+        query::With,
+        system::{Commands, Query, Res},
+    },
+    prelude::default,
+    render::{
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+            TextureViewDescriptor,
+        },
+        renderer::RenderDevice,
+        view::ViewTarget,
+    },
+};
+
+use crate::render::extract::ExtractedAutoExposure2d;
+
+use super::AutoExposureTextures;
+
+const AUTO_EXPOSURE_TEXTURE: &str = "auto_exposure_texture";
+const LUMINANCE_TEXTURE: &str = "auto_exposure_luminance_texture";
+
+/// The size of the luminance texture's largest mip level. Keep this in sync with
+/// `luminance.wgsl`.
+const LUMINANCE_SIZE: u32 = 256;
+
+pub fn prepare_auto_exposure_textures(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut view_targets: Query<
+        (
+            Entity,
+            Option<&ExtractedAutoExposure2d>,
+            Option<&mut AutoExposureTextures>,
+        ),
+        With<ViewTarget>,
+    >,
+) {
+    for (entity, auto_exposure, textures) in &mut view_targets {
+        match (auto_exposure, textures) {
+            (Some(_), Some(mut textures)) => {
+                textures.current = 1 - textures.current;
+            }
+            (Some(_), None) => {
+                // New textures are zeroed, which the shader treats as having no exposure yet.
+                let textures = [(); 2].map(|_| {
+                    render_device
+                        .create_texture(&TextureDescriptor {
+                            label: Some(AUTO_EXPOSURE_TEXTURE),
+                            size: Extent3d {
+                                width: 1,
+                                height: 1,
+                                depth_or_array_layers: 1,
+                            },
+                            mip_level_count: 1,
+                            sample_count: 1,
+                            dimension: TextureDimension::D2,
+                            format: TextureFormat::Rgba16Float,
+                            usage: TextureUsages::RENDER_ATTACHMENT
+                                | TextureUsages::TEXTURE_BINDING,
+                            view_formats: &[],
+                        })
+                        .create_view(&TextureViewDescriptor::default())
+                });
+
+                // Halving down to a single texel, so the last mip level holds the average.
+                let mip_level_count = LUMINANCE_SIZE.ilog2() + 1;
+                let luminance_texture = render_device.create_texture(&TextureDescriptor {
+                    label: Some(LUMINANCE_TEXTURE),
+                    size: Extent3d {
+                        width: LUMINANCE_SIZE,
+                        height: LUMINANCE_SIZE,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: TextureFormat::Rgba16Float,
+                    usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                });
+                let luminance = (0..mip_level_count)
+                    .map(|base_mip_level| {
+                        luminance_texture.create_view(&TextureViewDescriptor {
+                            label: Some(LUMINANCE_TEXTURE),
+                            base_mip_level,
+                            mip_level_count: Some(1),
+                            ..default()
+                        })
+                    })
+                    .collect();
+
+                commands.entity(entity).insert(AutoExposureTextures {
+                    textures,
+                    current: 0,
+                    luminance,
+                });
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<AutoExposureTextures>();
+            }
+            (None, None) => {}
+        }
+    }
+}
@@ -1,12 +1,36 @@
 use bevy::{
-    camera::primitives::Frustum,
+    camera::{primitives::Frustum, visibility::RenderLayers},
+    log::warn_once,
     prelude::*,
-    render::{Extract, render_resource::ShaderType, sync_world::RenderEntity},
+    render::{
+        Extract,
+        render_resource::{ShaderType, TextureDimension},
+        sync_world::{RenderEntity, TemporaryRenderEntity},
+    },
+    sprite::Anchor,
 };
 
 use crate::{
-    light::{Light2d, PointLight2d, SpotLight2d},
+    composite::{LightColorGrading2d, LightComposite2d, LightCompositeMode, LightCompositeSpace},
+    day_night::DayNightCycle2d,
+    exposure::AutoExposure2d,
+    fog::{Fog2d, FogVolume2d},
+    global_illumination::GlobalIllumination2d,
+    lens_flare::LensFlare2d,
+    light::{
+        AmbientLight2d, AmbientLight2dMapping, AmbientLight2dSource, AmbientZone2d,
+        AmbientZone2dShape, GlobalAmbientLight2d, Light2d, LightEmitterBatch2d, LightHalo2d,
+        PointLight2d, SpotLight2d,
+    },
+    modulation::LightModulation2d,
     occluder::{LightOccluder2d, LightOccluder2dShape},
+    reflection::{OccluderBox, mirror_images},
+    render::{
+        lens_flare::{ExtractedLensFlareElement, ExtractedLensFlares},
+        sprite_layers::{ExtractedLayerSprite, ExtractedSpriteLayers, SpriteLayer},
+    },
+    sprite::{Emissive2d, NormalMap2d, Specular2d, Unlit2d},
+    transient::TransientLight2d,
 };
 
 #[derive(Component, Default, Clone, ShaderType)]
@@ -17,6 +41,10 @@ pub struct ExtractedPointLight2d {
     pub intensity: f32,
     pub falloff: f32,
     pub cast_shadows: u32,
+    pub height: f32,
+    pub halo_intensity: f32,
+    pub halo_size: f32,
+    pub reflections: u32,
 }
 
 #[derive(Component, Default, Clone, ShaderType)]
@@ -31,6 +59,10 @@ pub struct ExtractedSpotLight2d {
     pub outer_angle: f32,
     pub source_width: f32,
     pub cast_shadows: u32,
+    pub height: f32,
+    pub halo_intensity: f32,
+    pub halo_size: f32,
+    pub reflections: u32,
 }
 
 pub fn extract_spot_lights(
@@ -41,17 +73,21 @@ pub fn extract_spot_lights(
             &SpotLight2d,
             &GlobalTransform,
             &InheritedVisibility,
+            Option<&LightModulation2d>,
         )>,
     >,
     frustum_query: Extract<Query<&Frustum, (With<Camera2d>, With<Light2d>)>>,
 ) {
-    for (render_entity, spot_light, global_transform, inherited_visibility) in &q {
+    for (render_entity, spot_light, global_transform, inherited_visibility, modulation) in &q {
         let center = global_transform.translation().xy();
+        let modulation = modulation.cloned().unwrap_or_default();
+        let radius = spot_light.radius * modulation.radius;
+        let halo = extract_halo(spot_light.halo.as_ref());
 
         let visible = inherited_visibility.get()
             && frustum_query
                 .iter()
-                .any(|frustum| circle_intersects_frustum(frustum, center, spot_light.radius));
+                .any(|frustum| circle_intersects_frustum(frustum, center, radius.max(halo.size)));
 
         if !visible {
             commands
@@ -67,28 +103,192 @@ pub fn extract_spot_lights(
             .entity(render_entity.id())
             .insert(ExtractedSpotLight2d {
                 center: global_transform.translation().xy(),
-                radius: spot_light.radius,
-                color: spot_light.color.to_linear(),
-                intensity: spot_light.intensity,
+                radius,
+                color: modulation.tint(spot_light.color),
+                intensity: spot_light.intensity * modulation.intensity,
                 falloff: spot_light.falloff,
                 direction: spotlight_direction,
                 inner_angle: inner_radians,
                 outer_angle: outer_radians,
                 source_width: spot_light.source_width,
                 cast_shadows: if spot_light.cast_shadows { 1 } else { 0 },
+                height: spot_light.height.max(0.0),
+                halo_intensity: halo.intensity * modulation.intensity,
+                halo_size: halo.size,
+                reflections: 0,
             });
     }
 }
 
+/// A light that doesn't cast shadows, and is shaded with a cheaper path than point lights.
+///
+/// Unlike other lights, these aren't stored as components on render entities. Instead, they're
+/// collected into [`ExtractedLightInstances`] and uploaded together as one buffer, then drawn as
+/// instanced quads so each only shades the pixels it covers.
+#[derive(Default, Clone, ShaderType)]
+pub struct ExtractedLightInstance2d {
+    pub center: Vec2,
+    pub radius: f32,
+    pub color: LinearRgba,
+    pub intensity: f32,
+    pub falloff: f32,
+}
+
+/// Light instances extracted this frame, waiting to be written to the GPU.
+#[derive(Resource, Default)]
+pub struct ExtractedLightInstances {
+    pub instances: Vec<ExtractedLightInstance2d>,
+}
+
+pub fn extract_transient_lights(
+    mut extracted_instances: ResMut<ExtractedLightInstances>,
+    transient_light_query: Extract<
+        Query<(&TransientLight2d, &GlobalTransform, &InheritedVisibility)>,
+    >,
+    frustum_query: Extract<Query<&Frustum, (With<Camera2d>, With<Light2d>)>>,
+) {
+    for (transient_light, global_transform, inherited_visibility) in &transient_light_query {
+        let center = global_transform.translation().xy();
+        let radius = transient_light.current_radius();
+
+        let visible = inherited_visibility.get()
+            && frustum_query
+                .iter()
+                .any(|frustum| circle_intersects_frustum(frustum, center, radius));
+
+        if !visible {
+            continue;
+        }
+
+        extracted_instances
+            .instances
+            .push(ExtractedLightInstance2d {
+                center,
+                radius,
+                color: transient_light.color.to_linear(),
+                intensity: transient_light.current_intensity(),
+                falloff: transient_light.falloff,
+            });
+    }
+}
+
+pub fn extract_light_emitter_batches(
+    mut extracted_instances: ResMut<ExtractedLightInstances>,
+    batch_query: Extract<Query<(&LightEmitterBatch2d, &GlobalTransform, &InheritedVisibility)>>,
+    frustum_query: Extract<Query<&Frustum, (With<Camera2d>, With<Light2d>)>>,
+) {
+    for (batch, global_transform, inherited_visibility) in &batch_query {
+        if !inherited_visibility.get() {
+            continue;
+        }
+
+        // Emitters scale with the batch, like their offsets. Lights stay circular, so a
+        // non-uniform scale uses the larger axis.
+        let scale = global_transform.scale().xy().abs().max_element();
+
+        for emitter in &batch.emitters {
+            let center = global_transform
+                .transform_point(emitter.offset.extend(0.0))
+                .xy();
+            let radius = emitter.radius * scale;
+
+            if !frustum_query
+                .iter()
+                .any(|frustum| circle_intersects_frustum(frustum, center, radius))
+            {
+                continue;
+            }
+
+            extracted_instances
+                .instances
+                .push(ExtractedLightInstance2d {
+                    center,
+                    radius,
+                    color: emitter.color.to_linear(),
+                    intensity: emitter.intensity,
+                    falloff: batch.falloff,
+                });
+        }
+    }
+}
+
+/// The largest value a 16 bit float can hold, which the SDF stores for infinitely tall
+/// occluders. Infinity itself would turn into NaN when filtered. Keep this in sync with
+/// `light_map.wgsl`.
+pub const INFINITE_HEIGHT: f32 = 65504.0;
+
 #[derive(Component, Default, Clone, ShaderType)]
 pub struct ExtractedLightOccluder2d {
     pub half_size: Vec2,
     pub center: Vec2,
+    pub light_penetration: f32,
+    pub height: f32,
+    pub reflectivity: f32,
+    // WebGL2 uniform arrays need a 16 byte aligned stride.
+    pub _padding: f32,
+}
+
+/// The glow of an emissive [`LightOccluder2d`].
+#[derive(Default, Clone, ShaderType)]
+pub struct ExtractedOccluderEmission2d {
+    pub half_size: Vec2,
+    pub center: Vec2,
+    pub color: LinearRgba,
+    pub distance: f32,
 }
 
+/// Occluder emissions extracted this frame, waiting to be written to the GPU.
+#[derive(Resource, Default)]
+pub struct ExtractedOccluderEmissions {
+    pub emissions: Vec<ExtractedOccluderEmission2d>,
+}
+
+/// The most ambient zones shaded by a view, as they're stored in a uniform for WebGL2.
+pub const MAX_AMBIENT_ZONES: usize = 16;
+/// The most polygon vertices shared by a view's ambient zones. Vertices are packed in pairs.
+pub const MAX_AMBIENT_ZONE_VERTICES: usize = 64;
+
 #[derive(Component, Default, Clone, ShaderType)]
 pub struct ExtractedAmbientLight2d {
     pub color: LinearRgba,
+    pub texture_tint: LinearRgba,
+    /// The world space rectangle the texture is mapped over, as its min and max corners.
+    pub texture_rect: Vec4,
+    pub gradient_sky: LinearRgba,
+    pub gradient_ground: LinearRgba,
+    /// The world space rectangle the gradient is mapped over, as its min and max corners.
+    pub gradient_rect: Vec4,
+    pub texture_mapping: u32,
+    pub gradient_mapping: u32,
+    pub zone_count: u32,
+    pub zones: [ExtractedAmbientZone2d; MAX_AMBIENT_ZONES],
+    pub zone_vertices: [Vec4; MAX_AMBIENT_ZONE_VERTICES / 2],
+}
+
+pub const AMBIENT_MAPPING_SCREEN: u32 = 0;
+pub const AMBIENT_MAPPING_WORLD: u32 = 1;
+
+/// The texture a view's ambient light is sampled from.
+#[derive(Component)]
+pub struct ExtractedAmbientTexture(pub AssetId<Image>);
+
+pub const AMBIENT_ZONE_RECTANGLE: u32 = 0;
+pub const AMBIENT_ZONE_CIRCLE: u32 = 1;
+pub const AMBIENT_ZONE_POLYGON: u32 = 2;
+
+#[derive(Default, Clone, Copy, ShaderType)]
+pub struct ExtractedAmbientZone2d {
+    pub color: LinearRgba,
+    pub center: Vec2,
+    /// The half size of a rectangle, or the radius of a circle in `x`.
+    pub half_size: Vec2,
+    /// The direction of a rectangle's local x axis, which rotates it.
+    pub x_axis: Vec2,
+    pub shape: u32,
+    pub blend_distance: f32,
+    pub vertex_start: u32,
+    pub vertex_count: u32,
+    pub _padding: Vec2,
 }
 
 pub fn extract_point_lights(
@@ -99,16 +299,22 @@ pub fn extract_point_lights(
             &PointLight2d,
             &GlobalTransform,
             &InheritedVisibility,
+            Option<&LightModulation2d>,
         )>,
     >,
     frustum_query: Extract<Query<&Frustum, (With<Camera2d>, With<Light2d>)>>,
 ) {
-    for (render_entity, point_light, global_transform, inherited_visibility) in &point_light_query {
+    for (render_entity, point_light, global_transform, inherited_visibility, modulation) in
+        &point_light_query
+    {
         let center = global_transform.translation().xy();
+        let modulation = modulation.cloned().unwrap_or_default();
+        let radius = point_light.radius * modulation.radius;
+        let halo = extract_halo(point_light.halo.as_ref());
         let visible = inherited_visibility.get()
             && frustum_query
                 .iter()
-                .any(|frustum| circle_intersects_frustum(frustum, center, point_light.radius));
+                .any(|frustum| circle_intersects_frustum(frustum, center, radius.max(halo.size)));
 
         if !visible {
             commands
@@ -119,16 +325,205 @@ pub fn extract_point_lights(
         commands
             .entity(render_entity.id())
             .insert(ExtractedPointLight2d {
-                color: point_light.color.to_linear(),
+                color: modulation.tint(point_light.color),
                 transform: center,
-                radius: point_light.radius,
-                intensity: point_light.intensity,
+                radius,
+                intensity: point_light.intensity * modulation.intensity,
                 falloff: point_light.falloff,
                 cast_shadows: if point_light.cast_shadows { 1 } else { 0 },
+                height: point_light.height.max(0.0),
+                halo_intensity: halo.intensity * modulation.intensity,
+                halo_size: halo.size,
+                reflections: 0,
+            });
+    }
+}
+
+/// Extracts the mirror images of lights in reflective occluders, each as an extra light on a
+/// temporary render entity. See [`mirror_images`].
+pub fn extract_reflected_lights(
+    mut commands: Commands,
+    point_light_query: Extract<
+        Query<(
+            &PointLight2d,
+            &GlobalTransform,
+            &InheritedVisibility,
+            Option<&LightModulation2d>,
+        )>,
+    >,
+    spot_light_query: Extract<
+        Query<(
+            &SpotLight2d,
+            &GlobalTransform,
+            &InheritedVisibility,
+            Option<&LightModulation2d>,
+        )>,
+    >,
+    light_occluders_query: Extract<
+        Query<(
+            Entity,
+            &LightOccluder2d,
+            &GlobalTransform,
+            &InheritedVisibility,
+        )>,
+    >,
+    frustum_query: Extract<Query<&Frustum, (With<Camera2d>, With<Light2d>)>>,
+) {
+    let mirrors: Vec<_> = light_occluders_query
+        .iter()
+        .filter(|(.., inherited_visibility)| inherited_visibility.get())
+        .map(|(entity, occluder, global_transform, _)| {
+            OccluderBox::new(entity, occluder, global_transform)
+        })
+        .filter(OccluderBox::is_reflective)
+        .collect();
+
+    if mirrors.is_empty() {
+        return;
+    }
+
+    // An image may light the screen even when the light itself is off screen.
+    let visible = |center, radius| {
+        frustum_query
+            .iter()
+            .any(|frustum| circle_intersects_frustum(frustum, center, radius))
+    };
+
+    for (point_light, global_transform, inherited_visibility, modulation) in &point_light_query {
+        if !inherited_visibility.get() || !point_light.cast_shadows {
+            continue;
+        }
+
+        let center = global_transform.translation().xy();
+        let modulation = modulation.cloned().unwrap_or_default();
+        let radius = point_light.radius * modulation.radius;
+        let color = modulation.tint(point_light.color);
+
+        for image in mirror_images(&mirrors, center, radius, point_light.max_reflections) {
+            if !visible(image.position, radius) {
+                continue;
+            }
+
+            commands.spawn((
+                ExtractedPointLight2d {
+                    transform: image.position,
+                    radius,
+                    color: reflected_color(color, image.tint),
+                    intensity: point_light.intensity * modulation.intensity,
+                    falloff: point_light.falloff,
+                    cast_shadows: 1,
+                    height: point_light.height.max(0.0),
+                    halo_intensity: 0.0,
+                    halo_size: 0.0,
+                    reflections: image.reflections,
+                },
+                TemporaryRenderEntity,
+            ));
+        }
+    }
+
+    for (spot_light, global_transform, inherited_visibility, modulation) in &spot_light_query {
+        if !inherited_visibility.get() || !spot_light.cast_shadows {
+            continue;
+        }
+
+        let center = global_transform.translation().xy();
+        let modulation = modulation.cloned().unwrap_or_default();
+        let radius = spot_light.radius * modulation.radius;
+        let color = modulation.tint(spot_light.color);
+        let direction = Vec2::from_angle(spot_light.direction.to_radians());
+
+        for image in mirror_images(&mirrors, center, radius, spot_light.max_reflections) {
+            if !visible(image.position, radius) {
+                continue;
+            }
+
+            commands.spawn((
+                ExtractedSpotLight2d {
+                    center: image.position,
+                    radius,
+                    color: reflected_color(color, image.tint),
+                    intensity: spot_light.intensity * modulation.intensity,
+                    falloff: spot_light.falloff,
+                    direction: direction * image.flip,
+                    inner_angle: spot_light.inner_angle.to_radians(),
+                    outer_angle: spot_light.outer_angle.to_radians(),
+                    source_width: spot_light.source_width,
+                    cast_shadows: 1,
+                    height: spot_light.height.max(0.0),
+                    halo_intensity: 0.0,
+                    halo_size: 0.0,
+                    reflections: image.reflections,
+                },
+                TemporaryRenderEntity,
+            ));
+        }
+    }
+}
+
+fn reflected_color(color: LinearRgba, tint: Vec3) -> LinearRgba {
+    LinearRgba::from_vec3(color.to_vec3() * tint).with_alpha(color.alpha)
+}
+
+pub fn extract_lens_flares(
+    mut extracted_flares: ResMut<ExtractedLensFlares>,
+    flare_query: Extract<
+        Query<(
+            &LensFlare2d,
+            AnyOf<(&PointLight2d, &SpotLight2d)>,
+            &GlobalTransform,
+            &InheritedVisibility,
+            Option<&LightModulation2d>,
+        )>,
+    >,
+) {
+    for (
+        lens_flare,
+        (point_light, spot_light),
+        global_transform,
+        inherited_visibility,
+        modulation,
+    ) in &flare_query
+    {
+        if !inherited_visibility.get() {
+            continue;
+        }
+
+        let modulation = modulation.cloned().unwrap_or_default();
+        let light_color = match (point_light, spot_light) {
+            (Some(point_light), _) => modulation.tint(point_light.color),
+            (_, Some(spot_light)) => modulation.tint(spot_light.color),
+            (None, None) => continue,
+        };
+        let intensity = lens_flare.intensity.max(0.0) * modulation.intensity;
+
+        for element in &lens_flare.elements {
+            let color = element.color.to_linear().to_vec4() * light_color.to_vec4();
+            extracted_flares.elements.push(ExtractedLensFlareElement {
+                light_center: global_transform.translation().xy(),
+                position: element.position,
+                size: element.size.max(0.0),
+                color: (color.truncate() * intensity).extend(color.w),
+                image: element.image.as_ref().map(|image| image.id()),
             });
+        }
     }
 }
 
+/// Lights without a halo are given one with no intensity, which the shader skips.
+fn extract_halo(halo: Option<&LightHalo2d>) -> LightHalo2d {
+    halo.map_or(
+        LightHalo2d {
+            intensity: 0.0,
+            size: 0.0,
+        },
+        |halo| LightHalo2d {
+            intensity: halo.intensity.max(0.0),
+            size: halo.size.max(0.0),
+        },
+    )
+}
+
 fn circle_intersects_frustum(frustum: &Frustum, center: Vec2, radius: f32) -> bool {
     // Lights reach the GPU as 2d positions and the light map isn't depth tested, so z is dropped
     // here too, along with the near and far half-spaces that bound the camera in depth. Culling on
@@ -145,6 +540,7 @@ fn circle_intersects_frustum(frustum: &Frustum, center: Vec2, radius: f32) -> bo
 
 pub fn extract_light_occluders(
     mut commands: Commands,
+    mut extracted_emissions: ResMut<ExtractedOccluderEmissions>,
     light_occluders_query: Extract<
         Query<(
             &RenderEntity,
@@ -153,6 +549,7 @@ pub fn extract_light_occluders(
             &InheritedVisibility,
         )>,
     >,
+    frustum_query: Extract<Query<&Frustum, (With<Camera2d>, With<Light2d>)>>,
 ) {
     for (render_entity, light_occluder, global_transform, inherited_visibility) in
         &light_occluders_query
@@ -168,9 +565,30 @@ pub fn extract_light_occluders(
             LightOccluder2dShape::Rectangle { half_size } => ExtractedLightOccluder2d {
                 half_size,
                 center: global_transform.translation().xy(),
+                light_penetration: light_occluder.light_penetration.max(0.0),
+                height: light_occluder.height.clamp(0.0, INFINITE_HEIGHT),
+                reflectivity: light_occluder.reflectivity.clamp(0.0, 1.0),
+                ..default()
             },
         };
 
+        if let Some((color, distance)) = light_occluder.emission {
+            let reach = extracted_occluder.half_size.length() + distance;
+            if frustum_query
+                .iter()
+                .any(|frustum| circle_intersects_frustum(frustum, extracted_occluder.center, reach))
+            {
+                extracted_emissions
+                    .emissions
+                    .push(ExtractedOccluderEmission2d {
+                        half_size: extracted_occluder.half_size,
+                        center: extracted_occluder.center,
+                        color: color.to_linear(),
+                        distance: distance.max(0.0),
+                    });
+            }
+        }
+
         commands
             .entity(render_entity.id())
             .insert(extracted_occluder);
@@ -180,12 +598,701 @@ pub fn extract_light_occluders(
 pub fn extract_ambient_lights(
     mut commands: Commands,
     light_2d_query: Extract<Query<(&RenderEntity, &Light2d)>>,
+    global_ambient_light: Extract<Option<Res<GlobalAmbientLight2d>>>,
+    day_night_cycle: Extract<Option<Res<DayNightCycle2d>>>,
+    ambient_light_query: Extract<Query<&AmbientLight2d>>,
+    ambient_zone_query: Extract<Query<(&AmbientZone2d, &GlobalTransform, &InheritedVisibility)>>,
+    frustum_query: Extract<Query<&Frustum, (With<Camera2d>, With<Light2d>)>>,
 ) {
+    let mut global_sources = AmbientLightSources::default();
+    if let Some(ambient_light) = global_ambient_light.as_ref() {
+        global_sources.light.color = ambient_light.color.to_linear() * ambient_light.brightness;
+    }
+    if let Some(cycle) = day_night_cycle.as_ref() {
+        let tint = cycle.ambient_light().to_vec4() * global_sources.light.color.to_vec4();
+        global_sources.light.color = LinearRgba::from_vec4(tint);
+    }
+
+    let mut extracted = ExtractedAmbientLight2d::default();
+
+    // Zones are blended in order, so the highest zone is applied last.
+    let mut zones: Vec<_> = ambient_zone_query
+        .iter()
+        .filter(|(_, _, inherited_visibility)| inherited_visibility.get())
+        .map(|(zone, global_transform, _)| (zone, global_transform))
+        .filter(|(zone, global_transform)| {
+            frustum_query.iter().any(|frustum| {
+                circle_intersects_frustum(
+                    frustum,
+                    global_transform.translation().xy(),
+                    ambient_zone_reach(zone, global_transform),
+                )
+            })
+        })
+        .collect();
+    zones.sort_by(|(_, a), (_, b)| a.translation().z.total_cmp(&b.translation().z));
+
+    let mut vertex_count = 0;
+    for (zone, global_transform) in zones {
+        let Some(slot) = extracted.zones.get_mut(extracted.zone_count as usize) else {
+            break;
+        };
+
+        let (scale, rotation, translation) = global_transform.to_scale_rotation_translation();
+        let scale = scale.xy().abs();
+
+        let mut extracted_zone = ExtractedAmbientZone2d {
+            color: zone.color.to_linear() * zone.brightness,
+            center: translation.xy(),
+            x_axis: (rotation * Vec3::X).xy().normalize_or(Vec2::X),
+            blend_distance: zone.blend_distance.max(0.0),
+            ..default()
+        };
+
+        match &zone.shape {
+            AmbientZone2dShape::Rectangle { half_size } => {
+                extracted_zone.shape = AMBIENT_ZONE_RECTANGLE;
+                extracted_zone.half_size = half_size.abs() * scale;
+            }
+            AmbientZone2dShape::Circle { radius } => {
+                extracted_zone.shape = AMBIENT_ZONE_CIRCLE;
+                extracted_zone.half_size = Vec2::new(radius.abs() * scale.max_element(), 0.0);
+            }
+            AmbientZone2dShape::Polygon { vertices } => {
+                if vertices.len() < 3 {
+                    continue;
+                }
+                if vertex_count + vertices.len() > MAX_AMBIENT_ZONE_VERTICES {
+                    warn_once!(
+                        "Ambient zone polygons have more than {MAX_AMBIENT_ZONE_VERTICES} vertices \
+                        in total, so some zones are not drawn"
+                    );
+                    continue;
+                }
+
+                extracted_zone.shape = AMBIENT_ZONE_POLYGON;
+                extracted_zone.vertex_start = vertex_count as u32;
+                extracted_zone.vertex_count = vertices.len() as u32;
+
+                for vertex in vertices {
+                    let packed = &mut extracted.zone_vertices[vertex_count / 2];
+                    let vertex = global_transform.transform_point(vertex.extend(0.0)).xy();
+                    if vertex_count % 2 == 0 {
+                        packed.x = vertex.x;
+                        packed.y = vertex.y;
+                    } else {
+                        packed.z = vertex.x;
+                        packed.w = vertex.y;
+                    }
+                    vertex_count += 1;
+                }
+            }
+        }
+
+        *slot = extracted_zone;
+        extracted.zone_count += 1;
+    }
+
     for (render_entity, light_2d) in &light_2d_query {
+        let mut sources = match &light_2d.ambient_light {
+            Some(ambient_light) => {
+                let mut sources = AmbientLightSources::default();
+                sources.add(ambient_light);
+                sources
+            }
+            None => global_sources.clone(),
+        };
+
+        // Ambient light entities add to the camera's own ambient light, or the resource.
+        for ambient_light in &ambient_light_query {
+            sources.add(ambient_light);
+        }
+
+        let mut entity = commands.entity(render_entity.id());
+
+        match sources.texture {
+            Some(texture) => entity.insert(ExtractedAmbientTexture(texture)),
+            None => entity.remove::<ExtractedAmbientTexture>(),
+        };
+
+        entity.insert(ExtractedAmbientLight2d {
+            zone_count: extracted.zone_count,
+            zones: extracted.zones,
+            zone_vertices: extracted.zone_vertices,
+            ..sources.light
+        });
+    }
+}
+
+/// A view's ambient light before its zones are added, made up of a uniform color and at most one
+/// texture and one gradient.
+#[derive(Clone)]
+struct AmbientLightSources {
+    light: ExtractedAmbientLight2d,
+    texture: Option<AssetId<Image>>,
+    has_gradient: bool,
+}
+
+impl Default for AmbientLightSources {
+    fn default() -> Self {
+        Self {
+            light: ExtractedAmbientLight2d {
+                color: LinearRgba::BLACK,
+                texture_tint: LinearRgba::BLACK,
+                gradient_sky: LinearRgba::BLACK,
+                gradient_ground: LinearRgba::BLACK,
+                ..default()
+            },
+            texture: None,
+            has_gradient: false,
+        }
+    }
+}
+
+impl AmbientLightSources {
+    fn add(&mut self, ambient_light: &AmbientLight2d) {
+        let color = ambient_light_color(ambient_light);
+
+        match &ambient_light.source {
+            AmbientLight2dSource::Uniform => {
+                self.light.color += color;
+            }
+            AmbientLight2dSource::Texture { image, mapping } => {
+                if self.texture.is_some() {
+                    return;
+                }
+
+                self.texture = Some(image.id());
+                self.light.texture_tint = color;
+                (self.light.texture_mapping, self.light.texture_rect) = ambient_mapping(mapping);
+            }
+            AmbientLight2dSource::Gradient {
+                sky,
+                ground,
+                mapping,
+            } => {
+                if self.has_gradient {
+                    return;
+                }
+
+                self.has_gradient = true;
+                self.light.gradient_sky =
+                    LinearRgba::from_vec3(sky.to_linear().to_vec3() * color.to_vec3());
+                self.light.gradient_ground =
+                    LinearRgba::from_vec3(ground.to_linear().to_vec3() * color.to_vec3());
+                (self.light.gradient_mapping, self.light.gradient_rect) = ambient_mapping(mapping);
+            }
+        }
+    }
+}
+
+fn ambient_mapping(mapping: &AmbientLight2dMapping) -> (u32, Vec4) {
+    match mapping {
+        AmbientLight2dMapping::Screen => (AMBIENT_MAPPING_SCREEN, Vec4::ZERO),
+        AmbientLight2dMapping::World(rect) => {
+            // Keep the rectangle from collapsing, so the shader can divide by its size.
+            let max = rect.min + (rect.max - rect.min).max(Vec2::splat(1e-3));
+            (AMBIENT_MAPPING_WORLD, rect.min.extend(max.x).extend(max.y))
+        }
+    }
+}
+
+/// How far from its translation an ambient zone has an effect.
+fn ambient_zone_reach(zone: &AmbientZone2d, global_transform: &GlobalTransform) -> f32 {
+    let size = match &zone.shape {
+        AmbientZone2dShape::Rectangle { half_size } => half_size.length(),
+        AmbientZone2dShape::Circle { radius } => radius.abs(),
+        AmbientZone2dShape::Polygon { vertices } => vertices
+            .iter()
+            .map(|vertex| vertex.length())
+            .fold(0.0, f32::max),
+    };
+    let scale = global_transform.scale().xy().abs().max_element();
+    size * scale + zone.blend_distance.max(0.0)
+}
+
+fn ambient_light_color(ambient_light: &AmbientLight2d) -> LinearRgba {
+    ambient_light.color.to_linear() * ambient_light.brightness
+}
+
+#[derive(Component, Default, Clone, ShaderType)]
+pub struct ExtractedLightMapSettings {
+    pub fog_color: LinearRgba,
+    pub fog_texture_size: Vec2,
+    pub fog_texture_offset: Vec2,
+    pub emissive_blur: f32,
+    pub fog_density: f32,
+    pub fog_texture_density: f32,
+    pub ambient_occlusion_radius: f32,
+    pub ambient_occlusion_strength: f32,
+    pub fog_enabled: u32,
+    // WebGL2 structs must be 16 byte aligned.
+    pub _padding: Vec2,
+}
+
+/// The texture tiled across a camera's [`Fog2d`].
+#[derive(Component)]
+pub struct ExtractedFogTexture(pub AssetId<Image>);
+
+pub fn extract_light_map_settings(
+    mut commands: Commands,
+    light_2d_query: Extract<Query<(&RenderEntity, &Light2d, Option<&Fog2d>)>>,
+    fog_volume_query: Extract<Query<&InheritedVisibility, With<FogVolume2d>>>,
+) {
+    // Without any fog, the light map skips gathering it along rays to lights.
+    let any_fog_volumes = fog_volume_query
+        .iter()
+        .any(|inherited_visibility| inherited_visibility.get());
+
+    for (render_entity, light_2d, fog) in &light_2d_query {
+        let mut settings = ExtractedLightMapSettings {
+            emissive_blur: light_2d.emissive_blur.max(0.0),
+            ..default()
+        };
+
+        if let Some(ambient_occlusion) = &light_2d.ambient_occlusion {
+            settings.ambient_occlusion_radius = ambient_occlusion.radius.max(0.0);
+            settings.ambient_occlusion_strength = ambient_occlusion.strength.clamp(0.0, 1.0);
+        }
+
+        let mut entity = commands.entity(render_entity.id());
+
+        settings.fog_enabled = (fog.is_some() || any_fog_volumes) as u32;
+
+        if let Some(fog) = fog {
+            settings.fog_color = fog.color.to_linear();
+            settings.fog_density = fog.density.max(0.0);
+
+            if let Some(texture) = &fog.texture {
+                settings.fog_texture_size = texture.size.max(Vec2::splat(f32::EPSILON));
+                settings.fog_texture_offset = texture.offset;
+                settings.fog_texture_density = texture.density.max(0.0);
+                entity.insert(ExtractedFogTexture(texture.image.id()));
+            } else {
+                entity.remove::<ExtractedFogTexture>();
+            }
+        } else {
+            entity.remove::<ExtractedFogTexture>();
+        }
+
+        entity.insert(settings);
+    }
+}
+
+#[derive(Component, Default, Clone, ShaderType)]
+pub struct ExtractedGlobalIllumination2d {
+    pub intensity: f32,
+    pub max_distance: f32,
+    pub ray_count: u32,
+    pub probe_spacing: u32,
+}
+
+pub fn extract_global_illumination(
+    mut commands: Commands,
+    light_2d_query: Extract<Query<(&RenderEntity, Option<&GlobalIllumination2d>), With<Light2d>>>,
+) {
+    for (render_entity, global_illumination) in &light_2d_query {
+        let mut entity = commands.entity(render_entity.id());
+
+        match global_illumination {
+            Some(global_illumination) => {
+                entity.insert(ExtractedGlobalIllumination2d {
+                    intensity: global_illumination.intensity.max(0.0),
+                    max_distance: global_illumination.max_distance.max(0.0),
+                    ray_count: global_illumination.ray_count.max(1),
+                    probe_spacing: global_illumination.probe_spacing.max(1),
+                });
+            }
+            None => {
+                entity.remove::<ExtractedGlobalIllumination2d>();
+            }
+        }
+    }
+}
+
+pub fn extract_fog_volumes(
+    mut extracted_sprites: ResMut<ExtractedSpriteLayers>,
+    volume_query: Extract<
+        Query<(
+            &FogVolume2d,
+            &GlobalTransform,
+            &InheritedVisibility,
+            Option<&RenderLayers>,
+        )>,
+    >,
+) {
+    for (volume, global_transform, inherited_visibility, render_layers) in &volume_query {
+        if !inherited_visibility.get() {
+            continue;
+        }
+
+        // Volumes are drawn as plain quads, with the falloff given as a fraction of their size.
+        let size = volume.half_size * 2.0;
+        let world_size = size * global_transform.scale().xy().abs();
+        let falloff = volume.falloff.max(0.0) / world_size.max(Vec2::splat(f32::EPSILON));
+
+        extracted_sprites.sprites.push(ExtractedLayerSprite {
+            layer: SpriteLayer::Fog,
+            transform: *global_transform,
+            // The default image is plain white.
+            sprite_image: AssetId::default(),
+            layer_image: AssetId::default(),
+            color: Vec4::new(volume.density.max(0.0), falloff.x, falloff.y, 1.0),
+            anchor: Vec2::ZERO,
+            rect: None,
+            custom_size: Some(size),
+            flip_x: false,
+            flip_y: false,
+            render_layers: render_layers.cloned().unwrap_or_default(),
+        });
+    }
+}
+
+pub fn extract_emissive_sprites(
+    mut extracted_sprites: ResMut<ExtractedSpriteLayers>,
+    texture_atlases: Extract<Res<Assets<TextureAtlasLayout>>>,
+    sprite_query: Extract<
+        Query<(
+            &Sprite,
+            &Emissive2d,
+            &Anchor,
+            &GlobalTransform,
+            &ViewVisibility,
+            Option<&RenderLayers>,
+        )>,
+    >,
+) {
+    for (sprite, emissive, anchor, global_transform, view_visibility, render_layers) in
+        &sprite_query
+    {
+        if !view_visibility.get() {
+            continue;
+        }
+
+        let mut extracted_sprite = extract_layer_sprite(
+            SpriteLayer::Emissive,
+            sprite,
+            anchor,
+            global_transform,
+            render_layers,
+            &texture_atlases,
+        );
+
+        // An emission map is drawn as is, otherwise the sprite glows in its own colors.
+        let color = match &emissive.image {
+            Some(image) => {
+                extracted_sprite.layer_image = image.id();
+                LinearRgba::WHITE
+            }
+            None => sprite.color.to_linear(),
+        };
+        extracted_sprite.color = (color.to_vec3() * emissive.intensity).extend(color.alpha);
+
+        extracted_sprites.sprites.push(extracted_sprite);
+    }
+}
+
+/// A component that marks sprites for one of the [`SpriteLayer`]s.
+pub trait LayerSpriteMarker: Component {
+    /// The layer marked sprites are drawn into.
+    const LAYER: SpriteLayer;
+
+    /// Writes a sprite's layer data into its color and layer image. `marker` is `None` for
+    /// unmarked sprites drawn over marked ones.
+    fn encode(marker: Option<&Self>, sprite: &Sprite, extracted_sprite: &mut ExtractedLayerSprite);
+}
+
+impl LayerSpriteMarker for Unlit2d {
+    const LAYER: SpriteLayer = SpriteLayer::Unlit;
+
+    fn encode(unlit: Option<&Self>, sprite: &Sprite, extracted_sprite: &mut ExtractedLayerSprite) {
+        // The mask holds how much of the light map is ignored, so lit sprites write zero.
+        let unlit_amount = unlit.map_or(0.0, |unlit| 1.0 - unlit.lighting_weight.clamp(0.0, 1.0));
+        extracted_sprite.color = Vec4::new(unlit_amount, 0.0, 0.0, sprite.color.alpha());
+    }
+}
+
+impl LayerSpriteMarker for NormalMap2d {
+    const LAYER: SpriteLayer = SpriteLayer::Normal;
+
+    fn encode(
+        normal_map: Option<&Self>,
+        sprite: &Sprite,
+        extracted_sprite: &mut ExtractedLayerSprite,
+    ) {
+        // The red channel holds how strongly the normal map applies, so sprites without one
+        // cover it up with flat normals.
+        let strength = match normal_map {
+            Some(normal_map) => {
+                extracted_sprite.layer_image = normal_map.0.id();
+                1.0
+            }
+            None => 0.0,
+        };
+        extracted_sprite.color = Vec4::new(strength, 0.0, 0.0, sprite.color.alpha());
+    }
+}
+
+impl LayerSpriteMarker for Specular2d {
+    const LAYER: SpriteLayer = SpriteLayer::Specular;
+
+    fn encode(
+        specular: Option<&Self>,
+        sprite: &Sprite,
+        extracted_sprite: &mut ExtractedLayerSprite,
+    ) {
+        // Intensity, gloss, and whether a specular map is used, are carried in the color. Matte
+        // sprites write no intensity, covering up the highlights behind them.
+        let (intensity, gloss, uses_map) = match specular {
+            Some(specular) => {
+                let uses_map = match &specular.image {
+                    Some(image) => {
+                        extracted_sprite.layer_image = image.id();
+                        1.0
+                    }
+                    None => 0.0,
+                };
+                (
+                    specular.intensity.max(0.0),
+                    specular.gloss.max(1.0),
+                    uses_map,
+                )
+            }
+            None => (0.0, 1.0, 0.0),
+        };
+        extracted_sprite.color = Vec4::new(intensity, gloss, uses_map, sprite.color.alpha());
+    }
+}
+
+/// Extracts the sprites drawn into `M`'s layer, in a pass of their own after the main pass.
+///
+/// Unmarked sprites drawn over a marked sprite need to cover it up, so every visible sprite above
+/// the lowest marked sprite is extracted, every frame. A single marked sprite at the back of the
+/// scene means drawing nearly every sprite a second time. Views only draw the sprites sharing
+/// their render layers.
+pub fn extract_layer_sprites<M: LayerSpriteMarker>(
+    mut extracted_sprites: ResMut<ExtractedSpriteLayers>,
+    texture_atlases: Extract<Res<Assets<TextureAtlasLayout>>>,
+    sprite_query: Extract<
+        Query<(
+            &Sprite,
+            Option<&M>,
+            &Anchor,
+            &GlobalTransform,
+            &ViewVisibility,
+            Option<&RenderLayers>,
+        )>,
+    >,
+) {
+    let Some(lowest_marked) = sprite_query
+        .iter()
+        .filter(|(_, marker, .., view_visibility, _)| marker.is_some() && view_visibility.get())
+        .map(|(.., global_transform, _, _)| global_transform.translation().z)
+        .min_by(f32::total_cmp)
+    else {
+        return;
+    };
+
+    for (sprite, marker, anchor, global_transform, view_visibility, render_layers) in &sprite_query
+    {
+        if !view_visibility.get()
+            || (marker.is_none() && global_transform.translation().z <= lowest_marked)
+        {
+            continue;
+        }
+
+        let mut extracted_sprite = extract_layer_sprite(
+            M::LAYER,
+            sprite,
+            anchor,
+            global_transform,
+            render_layers,
+            &texture_atlases,
+        );
+        M::encode(marker, sprite, &mut extracted_sprite);
+
+        extracted_sprites.sprites.push(extracted_sprite);
+    }
+}
+
+fn extract_layer_sprite(
+    layer: SpriteLayer,
+    sprite: &Sprite,
+    anchor: &Anchor,
+    global_transform: &GlobalTransform,
+    render_layers: Option<&RenderLayers>,
+    texture_atlases: &Assets<TextureAtlasLayout>,
+) -> ExtractedLayerSprite {
+    // Combines the atlas and sprite rects the same way `bevy_sprite_render` does.
+    let atlas_rect = sprite
+        .texture_atlas
+        .as_ref()
+        .and_then(|atlas| atlas.texture_rect(texture_atlases))
+        .map(|rect| rect.as_rect());
+    let rect = match (atlas_rect, sprite.rect) {
+        (None, None) => None,
+        (None, Some(sprite_rect)) => Some(sprite_rect),
+        (Some(atlas_rect), None) => Some(atlas_rect),
+        (Some(atlas_rect), Some(mut sprite_rect)) => {
+            sprite_rect.min += atlas_rect.min;
+            sprite_rect.max += atlas_rect.min;
+            Some(sprite_rect)
+        }
+    };
+
+    ExtractedLayerSprite {
+        layer,
+        transform: *global_transform,
+        sprite_image: sprite.image.id(),
+        layer_image: sprite.image.id(),
+        color: sprite.color.to_linear().to_vec4(),
+        anchor: anchor.as_vec(),
+        rect,
+        custom_size: sprite.custom_size,
+        flip_x: sprite.flip_x,
+        flip_y: sprite.flip_y,
+        render_layers: render_layers.cloned().unwrap_or_default(),
+    }
+}
+
+#[derive(Component, Default, Clone, ShaderType)]
+pub struct ExtractedAutoExposure2d {
+    pub min_exposure: f32,
+    pub max_exposure: f32,
+    pub brighten_speed: f32,
+    pub darken_speed: f32,
+    pub delta_time: f32,
+    // WebGL2 structs must be 16 byte aligned.
+    pub _padding: Vec3,
+}
+
+pub fn extract_auto_exposure(
+    mut commands: Commands,
+    time: Extract<Res<Time>>,
+    light_2d_query: Extract<Query<(&RenderEntity, Option<&AutoExposure2d>), With<Light2d>>>,
+) {
+    for (render_entity, auto_exposure) in &light_2d_query {
+        let mut entity = commands.entity(render_entity.id());
+
+        match auto_exposure {
+            Some(auto_exposure) => {
+                let min_exposure = auto_exposure.min_exposure.max(f32::EPSILON);
+                entity.insert(ExtractedAutoExposure2d {
+                    min_exposure,
+                    max_exposure: auto_exposure.max_exposure.max(min_exposure),
+                    brighten_speed: auto_exposure.brighten_speed.max(0.0),
+                    darken_speed: auto_exposure.darken_speed.max(0.0),
+                    delta_time: time.delta_secs(),
+                    ..default()
+                });
+            }
+            None => {
+                entity.remove::<ExtractedAutoExposure2d>();
+            }
+        }
+    }
+}
+
+pub const LIGHT_COMPOSITE_MULTIPLY: u32 = 0;
+pub const LIGHT_COMPOSITE_SOFT_LIGHT: u32 = 1;
+pub const LIGHT_COMPOSITE_OVERLAY: u32 = 2;
+pub const LIGHT_COMPOSITE_MULTIPLY_ADDITIVE: u32 = 3;
+
+#[derive(Component, Default, Clone, ShaderType)]
+pub struct ExtractedLightComposite2d {
+    pub mode: u32,
+    pub gamma: u32,
+    pub exposure: f32,
+    // WebGL2 structs must be 16 byte aligned.
+    pub _padding: f32,
+}
+
+pub fn extract_light_composites(
+    mut commands: Commands,
+    light_2d_query: Extract<Query<(&RenderEntity, Option<&LightComposite2d>), With<Light2d>>>,
+) {
+    for (render_entity, composite) in &light_2d_query {
+        let composite = composite.cloned().unwrap_or_default();
+
         commands
             .entity(render_entity.id())
-            .insert(ExtractedAmbientLight2d {
-                color: light_2d.ambient_light.color.to_linear() * light_2d.ambient_light.brightness,
+            .insert(ExtractedLightComposite2d {
+                mode: match composite.mode {
+                    LightCompositeMode::Multiply => LIGHT_COMPOSITE_MULTIPLY,
+                    LightCompositeMode::SoftLight => LIGHT_COMPOSITE_SOFT_LIGHT,
+                    LightCompositeMode::Overlay => LIGHT_COMPOSITE_OVERLAY,
+                    LightCompositeMode::MultiplyAdditive => LIGHT_COMPOSITE_MULTIPLY_ADDITIVE,
+                },
+                gamma: (composite.space == LightCompositeSpace::Gamma) as u32,
+                exposure: composite.exposure.max(0.0),
+                ..default()
             });
     }
 }
+
+pub const LIGHT_GRADING_NONE: u32 = 0;
+pub const LIGHT_GRADING_CURVES: u32 = 1;
+pub const LIGHT_GRADING_LUT: u32 = 2;
+
+/// The number of samples each color grading curve is baked into.
+pub const LIGHT_GRADING_CURVE_SAMPLES: usize = 32;
+
+#[derive(Component, Default, Clone, ShaderType)]
+pub struct ExtractedLightColorGrading2d {
+    pub mode: u32,
+    /// The red, green and blue curves, baked into evenly spaced samples from `0.0` to `1.0`.
+    pub curves: [Vec4; LIGHT_GRADING_CURVE_SAMPLES],
+}
+
+/// The 3D lookup table a camera's light map is graded with.
+#[derive(Component)]
+pub struct ExtractedLightLut(pub AssetId<Image>);
+
+pub fn extract_light_color_grading(
+    mut commands: Commands,
+    images: Extract<Res<Assets<Image>>>,
+    light_2d_query: Extract<Query<(&RenderEntity, Option<&LightColorGrading2d>), With<Light2d>>>,
+) {
+    for (render_entity, color_grading) in &light_2d_query {
+        let mut entity = commands.entity(render_entity.id());
+        let mut extracted = ExtractedLightColorGrading2d {
+            mode: LIGHT_GRADING_NONE,
+            ..default()
+        };
+
+        match color_grading {
+            Some(LightColorGrading2d::Lut(image)) => {
+                // Grading with a missing or 2d texture would break the light map, so it's
+                // skipped until a 3d texture is loaded.
+                let is_3d = images.get(image).is_some_and(|image| {
+                    image.texture_descriptor.dimension == TextureDimension::D3
+                });
+
+                if is_3d {
+                    extracted.mode = LIGHT_GRADING_LUT;
+                    entity.insert(ExtractedLightLut(image.id()));
+                } else {
+                    entity.remove::<ExtractedLightLut>();
+                }
+            }
+            Some(LightColorGrading2d::Curves(curves)) => {
+                extracted.mode = LIGHT_GRADING_CURVES;
+                for (i, sample) in extracted.curves.iter_mut().enumerate() {
+                    let t = i as f32 / (LIGHT_GRADING_CURVE_SAMPLES - 1) as f32;
+                    *sample = Vec4::new(
+                        curves.red.sample_clamped(t),
+                        curves.green.sample_clamped(t),
+                        curves.blue.sample_clamped(t),
+                        0.0,
+                    );
+                }
+                entity.remove::<ExtractedLightLut>();
+            }
+            None => {
+                entity.remove::<ExtractedLightLut>();
+            }
+        }
+
+        entity.insert(extracted);
+    }
+}
@@ -0,0 +1,117 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_render::view::View
+#import bevy_light_2d::types::GlobalIllumination2d
+#import bevy_light_2d::view_transformations::{ndc_to_uv, ndc_to_world, uv_to_ndc, world_to_ndc};
+
+const TAU: f32 = 6.28318530718;
+
+// Rays step at least this many times over their length, so they can't skip
+// over small emissive sprites in open space.
+const MIN_STEPS: u32 = 32u;
+
+// Rays give up after this many steps, however far they've travelled. Steps
+// shrink to half a probe near surfaces, so a long ray grazing a wall could
+// otherwise take thousands of them.
+const MAX_STEPS: u32 = 64u;
+
+@group(0) @binding(0)
+var<uniform> view: View;
+
+@group(0) @binding(1)
+var<uniform> settings: GlobalIllumination2d;
+
+@group(0) @binding(2)
+var sdf: texture_2d<f32>;
+
+@group(0) @binding(3)
+var sdf_sampler: sampler;
+
+@group(0) @binding(4)
+var light_map: texture_2d<f32>;
+
+@group(0) @binding(5)
+var scene: texture_2d<f32>;
+
+@group(0) @binding(6)
+var emissive: texture_2d<f32>;
+
+@group(0) @binding(7)
+var scene_sampler: sampler;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // Probes cover the whole screen, so their position comes from the UV
+    // rather than the (reduced) fragment coordinate.
+    let pos = ndc_to_world(uv_to_ndc(in.uv));
+
+    // The spacing between probes, in world units.
+    let probe_size = max(length(dpdx(pos)), 1e-4);
+
+    // Probes inside occluders receive no bounced light.
+    if get_distance(pos) <= 0.0 {
+        return vec4(0.0, 0.0, 0.0, 1.0);
+    }
+
+    // Rotate each probe's rays by a different amount, trading banding for
+    // noise that's smoothed out when the probes are upscaled.
+    let jitter = interleaved_gradient_noise(in.position.xy);
+
+    var radiance = vec3(0.0);
+    for (var i = 0u; i < settings.ray_count; i++) {
+        let angle = TAU * (f32(i) + jitter) / f32(settings.ray_count);
+        radiance += trace(pos, vec2(cos(angle), sin(angle)), probe_size);
+    }
+
+    return vec4(radiance / f32(settings.ray_count) * settings.intensity, 1.0);
+}
+
+// Marches a ray through the SDF, returning the light it picks up from the
+// first emissive sprite or occluder it reaches.
+fn trace(origin: vec2<f32>, direction: vec2<f32>, probe_size: f32) -> vec3<f32> {
+    let max_step = max(settings.max_distance / f32(MIN_STEPS), probe_size);
+
+    var travelled = probe_size;
+    for (var i = 0u; i < MAX_STEPS && travelled < settings.max_distance; i++) {
+        let pos = origin + direction * travelled;
+        let uv = ndc_to_uv(world_to_ndc(pos));
+
+        // Nothing is known about the scene beyond the edge of the screen.
+        if any(uv < vec2(0.0)) || any(uv > vec2(1.0)) {
+            break;
+        }
+
+        let emission = textureSampleLevel(emissive, scene_sampler, uv, 0.0).rgb;
+        if any(emission > vec3(0.0)) {
+            return emission;
+        }
+
+        let dist = textureSampleLevel(sdf, sdf_sampler, uv, 0.0).r;
+        if dist < probe_size * 0.5 {
+            // The light falling on the side of the occluder facing the probe,
+            // reflected in the occluder's own color.
+            let light = sample_uv(light_map, pos - direction * probe_size).rgb;
+            let albedo = sample_uv(scene, pos + direction * probe_size).rgb;
+            return light * albedo;
+        }
+
+        travelled += clamp(dist, probe_size * 0.5, max_step);
+    }
+
+    return vec3(0.0);
+}
+
+fn get_distance(pos: vec2<f32>) -> f32 {
+    let uv = ndc_to_uv(world_to_ndc(pos));
+    return textureSampleLevel(sdf, sdf_sampler, uv, 0.0).r;
+}
+
+fn sample_uv(texture: texture_2d<f32>, pos: vec2<f32>) -> vec4<f32> {
+    let uv = ndc_to_uv(world_to_ndc(pos));
+    return textureSampleLevel(texture, scene_sampler, uv, 0.0);
+}
+
+// Jimenez 2014, "Next Generation Post Processing in Call of Duty: Advanced
+// Warfare".
+fn interleaved_gradient_noise(frag_coord: vec2<f32>) -> f32 {
+    return fract(52.9829189 * fract(dot(frag_coord, vec2(0.06711056, 0.00583715))));
+}
@@ -0,0 +1,23 @@
+mod node;
+mod pipeline;
+mod prepare;
+
+use bevy::{
+    asset::{Handle, weak_handle},
+    ecs::component::Component,
+    render::texture::CachedTexture,
+    shader::Shader,
+};
+
+pub use node::global_illumination_pass;
+pub use pipeline::GlobalIlluminationPipeline;
+pub use prepare::prepare_global_illumination_texture;
+
+pub const GLOBAL_ILLUMINATION_SHADER: Handle<Shader> =
+    weak_handle!("a4e8c2d7-3b19-4f6a-9e05-7c2b8d1f4e93");
+
+/// Bounced light gathered by the probes, at a reduced resolution.
+#[derive(Component)]
+pub struct GlobalIlluminationTexture {
+    pub probes: CachedTexture,
+}
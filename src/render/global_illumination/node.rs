@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use bevy::render::extract_component::{ComponentUniforms, DynamicUniformIndex};
+use bevy::render::render_resource::{
+    BindGroupEntries, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+};
+use bevy::render::renderer::{RenderContext, ViewQuery};
+use bevy::render::texture::FallbackImageZero;
+use bevy::render::view::{ViewTarget, ViewUniformOffset, ViewUniforms};
+
+use crate::render::extract::ExtractedGlobalIllumination2d;
+use crate::render::light_map::LightMapTexture;
+use crate::render::sdf::SdfTexture;
+use crate::render::sprite_layers::{SpriteLayer, SpriteLayerTextures};
+
+use super::{GlobalIlluminationPipeline, GlobalIlluminationTexture};
+
+const GLOBAL_ILLUMINATION_PASS: &str = "global_illumination_pass";
+const GLOBAL_ILLUMINATION_BIND_GROUP: &str = "global_illumination_bind_group";
+
+pub fn global_illumination_pass(
+    world: &World,
+    view: ViewQuery<(
+        &ViewTarget,
+        &ViewUniformOffset,
+        &DynamicUniformIndex<ExtractedGlobalIllumination2d>,
+        &GlobalIlluminationTexture,
+        &LightMapTexture,
+        &SdfTexture,
+        &SpriteLayerTextures,
+    )>,
+    mut ctx: RenderContext,
+) {
+    let (
+        view_target,
+        view_offset,
+        settings_index,
+        global_illumination_texture,
+        light_map_texture,
+        sdf_texture,
+        sprite_layer_textures,
+    ) = view.into_inner();
+
+    let global_illumination_pipeline = world.resource::<GlobalIlluminationPipeline>();
+    let pipeline_cache = world.resource::<PipelineCache>();
+
+    let (Some(pipeline), Some(view_uniform_binding), Some(settings_binding)) = (
+        pipeline_cache.get_render_pipeline(global_illumination_pipeline.pipeline_id),
+        world.resource::<ViewUniforms>().uniforms.binding(),
+        world
+            .resource::<ComponentUniforms<ExtractedGlobalIllumination2d>>()
+            .uniforms()
+            .binding(),
+    ) else {
+        return;
+    };
+
+    // The main texture still holds the unlit scene, which gives surfaces their color.
+    let bind_group = ctx.render_device().create_bind_group(
+        GLOBAL_ILLUMINATION_BIND_GROUP,
+        &pipeline_cache.get_bind_group_layout(&global_illumination_pipeline.layout_descriptor),
+        &BindGroupEntries::sequential((
+            view_uniform_binding,
+            settings_binding,
+            &sdf_texture.sdf.default_view,
+            &global_illumination_pipeline.sdf_sampler,
+            &light_map_texture.light_map.default_view,
+            view_target.main_texture_view(),
+            sprite_layer_textures.view(
+                SpriteLayer::Emissive,
+                &world.resource::<FallbackImageZero>().texture_view,
+            ),
+            &global_illumination_pipeline.scene_sampler,
+        )),
+    );
+
+    let mut global_illumination_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some(GLOBAL_ILLUMINATION_PASS),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: &global_illumination_texture.probes.default_view,
+            resolve_target: None,
+            ops: Operations::default(),
+            depth_slice: None,
+        })],
+        ..default()
+    });
+
+    global_illumination_pass.set_render_pipeline(pipeline);
+    global_illumination_pass.set_bind_group(
+        0,
+        &bind_group,
+        &[view_offset.offset, settings_index.index()],
+    );
+    global_illumination_pass.draw(0..3, 0..1);
+}
@@ -0,0 +1,89 @@
+use bevy::core_pipeline::FullscreenShader;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::world::{FromWorld, World};
+use bevy::prelude::default;
+use bevy::render::render_resource::binding_types::{sampler, texture_2d, uniform_buffer};
+use bevy::render::render_resource::{
+    BindGroupLayoutDescriptor, BindGroupLayoutEntries, CachedRenderPipelineId, ColorTargetState,
+    ColorWrites, FilterMode, FragmentState, MultisampleState, PipelineCache, PrimitiveState,
+    RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages,
+    TextureFormat, TextureSampleType,
+};
+use bevy::render::renderer::RenderDevice;
+use bevy::render::view::ViewUniform;
+
+use crate::render::extract::ExtractedGlobalIllumination2d;
+
+use super::GLOBAL_ILLUMINATION_SHADER;
+
+const GLOBAL_ILLUMINATION_PIPELINE: &str = "global_illumination_pipeline";
+const GLOBAL_ILLUMINATION_BIND_GROUP_LAYOUT: &str = "global_illumination_bind_group_layout";
+
+#[derive(Resource)]
+pub struct GlobalIlluminationPipeline {
+    pub layout_descriptor: BindGroupLayoutDescriptor,
+    pub sdf_sampler: Sampler,
+    pub scene_sampler: Sampler,
+    pub pipeline_id: CachedRenderPipelineId,
+}
+
+impl FromWorld for GlobalIlluminationPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let layout_descriptor = BindGroupLayoutDescriptor::new(
+            GLOBAL_ILLUMINATION_BIND_GROUP_LAYOUT,
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    uniform_buffer::<ViewUniform>(true),
+                    uniform_buffer::<ExtractedGlobalIllumination2d>(true),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
+        let sdf_sampler = render_device.create_sampler(&SamplerDescriptor::default());
+        let scene_sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+        let fullscreen_shader = world.resource::<FullscreenShader>().clone();
+        let pipeline_id =
+            world
+                .resource_mut::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some(GLOBAL_ILLUMINATION_PIPELINE.into()),
+                    layout: vec![layout_descriptor.clone()],
+                    vertex: fullscreen_shader.to_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader: GLOBAL_ILLUMINATION_SHADER,
+                        shader_defs: vec![],
+                        entry_point: Some("fragment".into()),
+                        targets: vec![Some(ColorTargetState {
+                            format: TextureFormat::Rgba16Float,
+                            blend: None,
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    immediate_size: 0,
+                    zero_initialize_workgroup_memory: false,
+                });
+
+        Self {
+            layout_descriptor,
+            sdf_sampler,
+            scene_sampler,
+            pipeline_id,
+        }
+    }
+}
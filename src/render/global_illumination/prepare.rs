@@ -0,0 +1,61 @@
+use bevy::{
+    ecs::{
+        entity::Entity,
+        system::{Commands, Query, Res, ResMut},
+    },
+    render::{
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        renderer::RenderDevice,
+        texture::TextureCache,
+        view::ViewTarget,
+    },
+};
+
+use crate::render::extract::ExtractedGlobalIllumination2d;
+
+use super::GlobalIlluminationTexture;
+
+const GLOBAL_ILLUMINATION_TEXTURE: &str = "global_illumination_texture";
+
+pub fn prepare_global_illumination_texture(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    view_targets: Query<(Entity, &ViewTarget, Option<&ExtractedGlobalIllumination2d>)>,
+) {
+    for (entity, view_target, global_illumination) in &view_targets {
+        let Some(global_illumination) = global_illumination else {
+            commands
+                .entity(entity)
+                .remove::<GlobalIlluminationTexture>();
+            continue;
+        };
+
+        // One texel for each probe.
+        let size = view_target.main_texture().size();
+        let spacing = global_illumination.probe_spacing.max(1);
+        let probes = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some(GLOBAL_ILLUMINATION_TEXTURE),
+                size: Extent3d {
+                    width: size.width.div_ceil(spacing),
+                    height: size.height.div_ceil(spacing),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba16Float,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(GlobalIlluminationTexture { probes });
+    }
+}
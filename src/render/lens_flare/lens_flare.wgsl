@@ -0,0 +1,72 @@
+#import bevy_light_2d::types::LensFlareInstance
+#import bevy_light_2d::view_transformations::{
+    ndc_to_uv,
+    view,
+    world_to_ndc
+};
+
+@group(0) @binding(1)
+var<uniform> instance: LensFlareInstance;
+
+@group(0) @binding(2)
+var sdf: texture_2d<f32>;
+
+@group(0) @binding(3)
+var sdf_sampler: sampler;
+
+@group(1) @binding(0)
+var flare_texture: texture_2d<f32>;
+
+@group(1) @binding(1)
+var flare_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) visibility: f32,
+};
+
+@vertex
+fn vertex(@builtin(vertex_index) index: u32) -> VertexOutput {
+    // Quad corners, drawn as a triangle strip.
+    let corner = vec2(f32(index & 1u), f32((index & 2u) >> 1u));
+
+    // Elements are spaced along the line from the light to the center of the
+    // screen, which is the origin in NDC.
+    let light_ndc = world_to_ndc(instance.light_center);
+    let center = light_ndc * (1.0 - instance.position);
+    let half_size = instance.size / view.viewport.zw;
+
+    var out: VertexOutput;
+    out.position = vec4(center + (corner * 2.0 - 1.0) * half_size, 0.0, 1.0);
+    out.uv = vec2(corner.x, 1.0 - corner.y);
+    out.visibility = light_visibility(light_ndc);
+    return out;
+}
+
+// Fades the flare out as the light moves off screen, or its center sinks into
+// an occluder.
+fn light_visibility(light_ndc: vec2<f32>) -> f32 {
+    let on_screen = 1.0 - smoothstep(0.9, 1.0, max(abs(light_ndc.x), abs(light_ndc.y)));
+    if on_screen <= 0.0 {
+        return 0.0;
+    }
+
+    let dist = textureSampleLevel(sdf, sdf_sampler, ndc_to_uv(light_ndc), 0.0).r;
+    return on_screen * smoothstep(-1.0, 1.0, dist);
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = instance.color;
+
+    if instance.textured != 0u {
+        color *= textureSample(flare_texture, flare_sampler, in.uv);
+    } else {
+        // A soft round glow.
+        let falloff = max(1.0 - length(in.uv * 2.0 - 1.0), 0.0);
+        color.a *= falloff * falloff;
+    }
+
+    return vec4(color.rgb * color.a * in.visibility, 0.0);
+}
@@ -0,0 +1,65 @@
+mod node;
+mod pipeline;
+mod prepare;
+
+use bevy::{
+    asset::{AssetId, Handle, weak_handle},
+    ecs::{component::Component, resource::Resource},
+    image::Image,
+    math::{UVec3, Vec2, Vec4},
+    render::render_resource::{
+        CachedRenderPipelineId, DynamicUniformBuffer, ShaderType, TextureFormat,
+    },
+    shader::Shader,
+};
+
+pub use node::lens_flare_pass;
+pub use pipeline::LensFlarePipeline;
+pub use prepare::{prepare_lens_flare_pipelines, prepare_lens_flares};
+
+pub const LENS_FLARE_SHADER: Handle<Shader> = weak_handle!("6d3f2a91-8c47-4e0b-b5d2-1f9e7a4c3b68");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LensFlarePipelineKey {
+    pub target_format: TextureFormat,
+}
+
+#[derive(Component)]
+pub struct LensFlarePipelineId(pub CachedRenderPipelineId);
+
+/// A single element of a [`LensFlare2d`](crate::lens_flare::LensFlare2d).
+pub struct ExtractedLensFlareElement {
+    pub light_center: Vec2,
+    pub position: f32,
+    pub size: f32,
+    pub color: Vec4,
+    pub image: Option<AssetId<Image>>,
+}
+
+/// Lens flare elements extracted this frame, waiting to be written to the GPU.
+#[derive(Resource, Default)]
+pub struct ExtractedLensFlares {
+    pub elements: Vec<ExtractedLensFlareElement>,
+}
+
+#[derive(Default, Clone, ShaderType)]
+pub struct LensFlareInstance {
+    pub light_center: Vec2,
+    pub position: f32,
+    pub size: f32,
+    pub color: Vec4,
+    pub textured: u32,
+    // WebGL2 structs must be 16 byte aligned.
+    pub _padding: UVec3,
+}
+
+pub struct PreparedLensFlareElement {
+    pub image: AssetId<Image>,
+    pub instance_offset: u32,
+}
+
+#[derive(Resource, Default)]
+pub struct LensFlareMeta {
+    pub instances: DynamicUniformBuffer<LensFlareInstance>,
+    pub elements: Vec<PreparedLensFlareElement>,
+}
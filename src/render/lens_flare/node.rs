@@ -0,0 +1,106 @@
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_resource::{
+    BindGroup, BindGroupEntries, LoadOp, Operations, PipelineCache, RenderPassColorAttachment,
+    RenderPassDescriptor, StoreOp,
+};
+use bevy::render::renderer::{RenderContext, ViewQuery};
+use bevy::render::texture::GpuImage;
+use bevy::render::view::{ViewTarget, ViewUniformOffset, ViewUniforms};
+
+use crate::render::sdf::SdfTexture;
+
+use super::{LensFlareMeta, LensFlarePipeline, LensFlarePipelineId};
+
+const LENS_FLARE_PASS: &str = "lens_flare_pass";
+const LENS_FLARE_BIND_GROUP: &str = "lens_flare_bind_group";
+const LENS_FLARE_TEXTURE_BIND_GROUP: &str = "lens_flare_texture_bind_group";
+
+pub fn lens_flare_pass(
+    world: &World,
+    view: ViewQuery<(
+        &ViewTarget,
+        &ViewUniformOffset,
+        &LensFlarePipelineId,
+        &SdfTexture,
+    )>,
+    mut ctx: RenderContext,
+) {
+    let (view_target, view_offset, pipeline_id, sdf_texture) = view.into_inner();
+
+    let meta = world.resource::<LensFlareMeta>();
+    if meta.elements.is_empty() {
+        return;
+    }
+
+    let lens_flare_pipeline = world.resource::<LensFlarePipeline>();
+    let pipeline_cache = world.resource::<PipelineCache>();
+    let gpu_images = world.resource::<RenderAssets<GpuImage>>();
+
+    let (Some(pipeline), Some(view_uniform_binding), Some(instances_binding)) = (
+        pipeline_cache.get_render_pipeline(pipeline_id.0),
+        world.resource::<ViewUniforms>().uniforms.binding(),
+        meta.instances.binding(),
+    ) else {
+        return;
+    };
+
+    let bind_group = ctx.render_device().create_bind_group(
+        LENS_FLARE_BIND_GROUP,
+        &pipeline_cache.get_bind_group_layout(&lens_flare_pipeline.layout_descriptor),
+        &BindGroupEntries::sequential((
+            view_uniform_binding,
+            instances_binding,
+            &sdf_texture.sdf.default_view,
+            &lens_flare_pipeline.sdf_sampler,
+        )),
+    );
+
+    let mut texture_bind_groups: HashMap<AssetId<Image>, BindGroup> = HashMap::default();
+    for element in &meta.elements {
+        let Some(gpu_image) = gpu_images.get(element.image) else {
+            continue;
+        };
+
+        texture_bind_groups.entry(element.image).or_insert_with(|| {
+            ctx.render_device().create_bind_group(
+                LENS_FLARE_TEXTURE_BIND_GROUP,
+                &pipeline_cache
+                    .get_bind_group_layout(&lens_flare_pipeline.texture_layout_descriptor),
+                &BindGroupEntries::sequential((&gpu_image.texture_view, &gpu_image.sampler)),
+            )
+        });
+    }
+
+    // Flares are drawn over the lit scene.
+    let mut lens_flare_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some(LENS_FLARE_PASS),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: view_target.main_texture_view(),
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Load,
+                store: StoreOp::Store,
+            },
+            depth_slice: None,
+        })],
+        ..default()
+    });
+
+    lens_flare_pass.set_render_pipeline(pipeline);
+
+    for element in &meta.elements {
+        let Some(texture_bind_group) = texture_bind_groups.get(&element.image) else {
+            continue;
+        };
+
+        lens_flare_pass.set_bind_group(
+            0,
+            &bind_group,
+            &[view_offset.offset, element.instance_offset],
+        );
+        lens_flare_pass.set_bind_group(1, texture_bind_group, &[]);
+        lens_flare_pass.draw(0..4, 0..1);
+    }
+}
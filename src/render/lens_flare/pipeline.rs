@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::binding_types::{sampler, texture_2d, uniform_buffer};
+use bevy::render::render_resource::{
+    BindGroupLayoutDescriptor, BindGroupLayoutEntries, BlendComponent, BlendFactor, BlendOperation,
+    BlendState, ColorTargetState, ColorWrites, FragmentState, MultisampleState, PrimitiveState,
+    PrimitiveTopology, RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor,
+    ShaderStages, SpecializedRenderPipeline, TextureSampleType, VertexState,
+};
+use bevy::render::renderer::RenderDevice;
+use bevy::render::view::ViewUniform;
+
+use super::{LENS_FLARE_SHADER, LensFlareInstance, LensFlarePipelineKey};
+
+const LENS_FLARE_PIPELINE: &str = "lens_flare_pipeline";
+const LENS_FLARE_BIND_GROUP_LAYOUT: &str = "lens_flare_bind_group_layout";
+const LENS_FLARE_TEXTURE_BIND_GROUP_LAYOUT: &str = "lens_flare_texture_bind_group_layout";
+
+#[derive(Resource)]
+pub struct LensFlarePipeline {
+    pub layout_descriptor: BindGroupLayoutDescriptor,
+    pub texture_layout_descriptor: BindGroupLayoutDescriptor,
+    pub sdf_sampler: Sampler,
+}
+
+impl FromWorld for LensFlarePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        // The SDF is sampled in the vertex shader, to test whether the light is occluded.
+        let layout_descriptor = BindGroupLayoutDescriptor::new(
+            LENS_FLARE_BIND_GROUP_LAYOUT,
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::VERTEX_FRAGMENT,
+                (
+                    uniform_buffer::<ViewUniform>(true),
+                    uniform_buffer::<LensFlareInstance>(true),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
+        let texture_layout_descriptor = BindGroupLayoutDescriptor::new(
+            LENS_FLARE_TEXTURE_BIND_GROUP_LAYOUT,
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
+        let sdf_sampler = render_device.create_sampler(&SamplerDescriptor::default());
+
+        Self {
+            layout_descriptor,
+            texture_layout_descriptor,
+            sdf_sampler,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for LensFlarePipeline {
+    type Key = LensFlarePipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some(LENS_FLARE_PIPELINE.into()),
+            layout: vec![
+                self.layout_descriptor.clone(),
+                self.texture_layout_descriptor.clone(),
+            ],
+            vertex: VertexState {
+                shader: LENS_FLARE_SHADER,
+                shader_defs: vec![],
+                entry_point: Some("vertex".into()),
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: LENS_FLARE_SHADER,
+                shader_defs: vec![],
+                entry_point: Some("fragment".into()),
+                // Flares add light on top of the scene, leaving its alpha untouched.
+                targets: vec![Some(ColorTargetState {
+                    format: key.target_format,
+                    blend: Some(BlendState {
+                        color: BlendComponent {
+                            src_factor: BlendFactor::One,
+                            dst_factor: BlendFactor::One,
+                            operation: BlendOperation::Add,
+                        },
+                        alpha: BlendComponent {
+                            src_factor: BlendFactor::Zero,
+                            dst_factor: BlendFactor::One,
+                            operation: BlendOperation::Add,
+                        },
+                    }),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState {
+                topology: PrimitiveTopology::TriangleStrip,
+                ..default()
+            },
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            immediate_size: 0,
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
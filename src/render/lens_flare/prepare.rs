@@ -0,0 +1,78 @@
+use bevy::{
+    ecs::{
+        entity::Entity,
+        query::With,
+        system::{Commands, Query, Res, ResMut},
+    },
+    render::{
+        render_asset::RenderAssets,
+        render_resource::{PipelineCache, SpecializedRenderPipelines},
+        renderer::{RenderDevice, RenderQueue},
+        texture::GpuImage,
+        view::ExtractedView,
+    },
+};
+
+use crate::render::extract::ExtractedAmbientLight2d;
+
+use super::{
+    ExtractedLensFlares, LensFlareInstance, LensFlareMeta, LensFlarePipeline, LensFlarePipelineId,
+    LensFlarePipelineKey, PreparedLensFlareElement,
+};
+
+pub fn prepare_lens_flare_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<LensFlarePipeline>>,
+    lens_flare_pipeline: Res<LensFlarePipeline>,
+    view_targets: Query<(Entity, &ExtractedView), With<ExtractedAmbientLight2d>>,
+) {
+    for (entity, view) in view_targets.iter() {
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &lens_flare_pipeline,
+            LensFlarePipelineKey {
+                target_format: view.target_format,
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(LensFlarePipelineId(pipeline_id));
+    }
+}
+
+pub fn prepare_lens_flares(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
+    mut extracted_flares: ResMut<ExtractedLensFlares>,
+    mut meta: ResMut<LensFlareMeta>,
+) {
+    meta.instances.clear();
+    meta.elements.clear();
+
+    for element in extracted_flares.elements.drain(..) {
+        // Elements without an image are drawn as a glow, but still need an image to bind.
+        let image = element.image.unwrap_or_default();
+        if gpu_images.get(image).is_none() {
+            continue;
+        }
+
+        let instance_offset = meta.instances.push(&LensFlareInstance {
+            light_center: element.light_center,
+            position: element.position,
+            size: element.size,
+            color: element.color,
+            textured: element.image.is_some().into(),
+            ..Default::default()
+        });
+
+        meta.elements.push(PreparedLensFlareElement {
+            image,
+            instance_offset,
+        });
+    }
+
+    meta.instances.write_buffer(&render_device, &render_queue);
+}
@@ -0,0 +1,65 @@
+#import bevy_render::view::View
+#import bevy_light_2d::types::LightInstance2d
+
+// We're only using a single uniform binding for light instances in WebGL2,
+// which is limited to 4kb in BatchedUniformBuffer.
+const MAX_LIGHT_INSTANCES: u32 = 82u;
+
+@group(0) @binding(0)
+var<uniform> view: View;
+
+// Light instances (transient lights and emitter batches): SSBO on modern backends, UBO array on WebGL2
+#if AVAILABLE_STORAGE_BUFFER_BINDINGS >= 6
+    @group(0) @binding(1)
+    var<storage> light_instances: array<LightInstance2d>;
+#else
+    @group(0) @binding(1)
+    var<uniform> light_instances: array<LightInstance2d, MAX_LIGHT_INSTANCES>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) world_position: vec2<f32>,
+    @location(1) @interpolate(flat) instance: u32,
+};
+
+@vertex
+fn vertex(
+    @builtin(vertex_index) index: u32,
+    @builtin(instance_index) instance: u32,
+) -> VertexOutput {
+    let light = light_instances[instance];
+
+    // A quad covering the light's radius, drawn as a triangle strip.
+    let corner = vec2(f32(index & 1u), f32((index & 2u) >> 1u)) * 2.0 - 1.0;
+    let world_position = light.center + corner * light.radius;
+
+    var out: VertexOutput;
+    out.position = view.clip_from_world * vec4(world_position, 0.0, 1.0);
+    out.world_position = world_position;
+    out.instance = instance;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let light = light_instances[in.instance];
+    let dist = distance(light.center, in.world_position);
+    let light_color = light.color.rgb * attenuation(dist, light.radius, light.intensity, light.falloff);
+    return vec4(light_color, 0.0);
+}
+
+fn square(x: f32) -> f32 {
+    return x * x;
+}
+
+// Compute light attenutation, as for the light map's lights.
+// See https://lisyarus.github.io/blog/posts/point-light-attenuation.html
+fn attenuation(dist: f32, radius: f32, intensity: f32, falloff: f32) -> f32 {
+    let s = dist / radius;
+    if s > 1.0 {
+        return 0.0;
+    }
+    let s2 = square(s);
+    return intensity * square(1.0 - s2) / (1.0 + falloff * s2);
+}
@@ -0,0 +1,28 @@
+mod node;
+mod pipeline;
+mod prepare;
+
+use bevy::{
+    asset::{Handle, weak_handle},
+    ecs::{component::Component, resource::Resource},
+    render::texture::CachedTexture,
+    shader::Shader,
+};
+
+pub use node::light_instances_pass;
+pub use pipeline::LightInstancesPipeline;
+pub use prepare::{prepare_light_instances, prepare_light_instances_texture};
+
+pub const LIGHT_INSTANCES_SHADER: Handle<Shader> =
+    weak_handle!("c3f1a8e2-6d47-4b95-8e1c-2a9f0b7d5e36");
+
+/// Light from transient lights and emitter batches, drawn as additive quads before the light map
+/// reads it back. Each instance only shades the pixels it covers.
+#[derive(Component)]
+pub struct LightInstancesTexture {
+    pub texture: CachedTexture,
+}
+
+/// How many light instances were written to the GPU this frame.
+#[derive(Resource, Default)]
+pub struct LightInstanceCount(pub u32);
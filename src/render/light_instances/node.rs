@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    BindGroupEntries, GpuArrayBuffer, Operations, PipelineCache, RenderPassColorAttachment,
+    RenderPassDescriptor,
+};
+use bevy::render::renderer::{RenderContext, RenderDevice, ViewQuery};
+use bevy::render::view::{ViewUniformOffset, ViewUniforms};
+use smallvec::{SmallVec, smallvec};
+
+use crate::render::extract::ExtractedLightInstance2d;
+
+use super::{LightInstanceCount, LightInstancesPipeline, LightInstancesTexture};
+
+const LIGHT_INSTANCES_PASS: &str = "light_instances_pass";
+const LIGHT_INSTANCES_BIND_GROUP: &str = "light_instances_bind_group";
+
+// Matches `MAX_LIGHT_INSTANCES` in light_instances.wgsl, which keeps the uniform array used by
+// WebGL2 within 4kb.
+const MAX_LIGHT_INSTANCES: u32 = 82;
+
+pub fn light_instances_pass(
+    world: &World,
+    view: ViewQuery<(&ViewUniformOffset, &LightInstancesTexture)>,
+    mut ctx: RenderContext,
+) {
+    let (view_offset, light_instances_texture) = view.into_inner();
+
+    let light_instances_pipeline = world.resource::<LightInstancesPipeline>();
+    let pipeline_cache = world.resource::<PipelineCache>();
+    let count = world.resource::<LightInstanceCount>().0;
+
+    let bind_group = match (
+        pipeline_cache.get_render_pipeline(light_instances_pipeline.pipeline_id),
+        world.resource::<ViewUniforms>().uniforms.binding(),
+        world
+            .resource::<GpuArrayBuffer<ExtractedLightInstance2d>>()
+            .binding(),
+    ) {
+        (Some(pipeline), Some(view_uniform_binding), Some(light_instance_binding)) if count > 0 => {
+            Some((
+                pipeline,
+                ctx.render_device().create_bind_group(
+                    LIGHT_INSTANCES_BIND_GROUP,
+                    &pipeline_cache
+                        .get_bind_group_layout(&light_instances_pipeline.layout_descriptor),
+                    &BindGroupEntries::sequential((view_uniform_binding, light_instance_binding)),
+                ),
+            ))
+        }
+        _ => None,
+    };
+
+    // The texture is cleared even without instances, as the light map always reads it.
+    let mut light_instances_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some(LIGHT_INSTANCES_PASS),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: &light_instances_texture.texture.default_view,
+            resolve_target: None,
+            ops: Operations::default(),
+            depth_slice: None,
+        })],
+        ..default()
+    });
+
+    let Some((pipeline, bind_group)) = bind_group.as_ref() else {
+        return;
+    };
+
+    let mut offsets: SmallVec<[u32; 2]> = smallvec![view_offset.offset];
+
+    // WebGL2 falls back to a uniform array, limited to the instances that fit in one binding.
+    let storage_buffers_unavailable = world
+        .resource::<RenderDevice>()
+        .limits()
+        .max_storage_buffers_per_shader_stage
+        == 0;
+    let count = if storage_buffers_unavailable {
+        offsets.push(0);
+        count.min(MAX_LIGHT_INSTANCES)
+    } else {
+        count
+    };
+
+    light_instances_pass.set_render_pipeline(pipeline);
+    light_instances_pass.set_bind_group(0, bind_group, &offsets);
+    light_instances_pass.draw(0..4, 0..count);
+}
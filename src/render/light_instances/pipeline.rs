@@ -0,0 +1,89 @@
+use bevy::ecs::resource::Resource;
+use bevy::ecs::world::{FromWorld, World};
+use bevy::prelude::default;
+use bevy::render::render_resource::binding_types::uniform_buffer;
+use bevy::render::render_resource::{
+    BindGroupLayoutDescriptor, BindGroupLayoutEntries, BlendComponent, BlendFactor, BlendOperation,
+    BlendState, CachedRenderPipelineId, ColorTargetState, ColorWrites, FragmentState,
+    GpuArrayBuffer, MultisampleState, PipelineCache, PrimitiveState, PrimitiveTopology,
+    RenderPipelineDescriptor, ShaderStages, TextureFormat, VertexState,
+};
+use bevy::render::renderer::RenderDevice;
+use bevy::render::view::ViewUniform;
+
+use crate::render::extract::ExtractedLightInstance2d;
+
+use super::LIGHT_INSTANCES_SHADER;
+
+const LIGHT_INSTANCES_BIND_GROUP_LAYOUT: &str = "light_instances_bind_group_layout";
+const LIGHT_INSTANCES_PIPELINE: &str = "light_instances_pipeline";
+
+#[derive(Resource)]
+pub struct LightInstancesPipeline {
+    pub layout_descriptor: BindGroupLayoutDescriptor,
+    pub pipeline_id: CachedRenderPipelineId,
+}
+
+impl FromWorld for LightInstancesPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let limits = &render_device.limits();
+        let layout_descriptor = BindGroupLayoutDescriptor::new(
+            LIGHT_INSTANCES_BIND_GROUP_LAYOUT,
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::VERTEX_FRAGMENT,
+                (
+                    uniform_buffer::<ViewUniform>(true),
+                    GpuArrayBuffer::<ExtractedLightInstance2d>::binding_layout(limits),
+                ),
+            ),
+        );
+
+        // Overlapping lights add up.
+        let additive = BlendComponent {
+            src_factor: BlendFactor::One,
+            dst_factor: BlendFactor::One,
+            operation: BlendOperation::Add,
+        };
+
+        let pipeline_id =
+            world
+                .resource_mut::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some(LIGHT_INSTANCES_PIPELINE.into()),
+                    layout: vec![layout_descriptor.clone()],
+                    vertex: VertexState {
+                        shader: LIGHT_INSTANCES_SHADER,
+                        shader_defs: vec![],
+                        entry_point: Some("vertex".into()),
+                        buffers: vec![],
+                    },
+                    fragment: Some(FragmentState {
+                        shader: LIGHT_INSTANCES_SHADER,
+                        shader_defs: vec![],
+                        entry_point: Some("fragment".into()),
+                        targets: vec![Some(ColorTargetState {
+                            format: TextureFormat::Rgba16Float,
+                            blend: Some(BlendState {
+                                color: additive,
+                                alpha: additive,
+                            }),
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: PrimitiveState {
+                        topology: PrimitiveTopology::TriangleStrip,
+                        ..default()
+                    },
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    immediate_size: 0,
+                    zero_initialize_workgroup_memory: false,
+                });
+
+        Self {
+            layout_descriptor,
+            pipeline_id,
+        }
+    }
+}
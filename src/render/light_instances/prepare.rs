@@ -0,0 +1,66 @@
+use bevy::{
+    ecs::{
+        entity::Entity,
+        query::With,
+        system::{Commands, Query, Res, ResMut},
+    },
+    render::{
+        render_resource::{
+            GpuArrayBuffer, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        texture::TextureCache,
+        view::ViewTarget,
+    },
+};
+
+use crate::render::extract::{
+    ExtractedAmbientLight2d, ExtractedLightInstance2d, ExtractedLightInstances,
+};
+
+use super::{LightInstanceCount, LightInstancesTexture};
+
+const LIGHT_INSTANCES_TEXTURE: &str = "light_instances_texture";
+
+pub fn prepare_light_instances_texture(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    view_targets: Query<(Entity, &ViewTarget), With<ExtractedAmbientLight2d>>,
+) {
+    for (entity, view_target) in &view_targets {
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some(LIGHT_INSTANCES_TEXTURE),
+                size: view_target.main_texture().size(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba16Float,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(LightInstancesTexture { texture });
+    }
+}
+
+pub fn prepare_light_instances(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut extracted_instances: ResMut<ExtractedLightInstances>,
+    mut light_instances: ResMut<GpuArrayBuffer<ExtractedLightInstance2d>>,
+    mut light_instance_count: ResMut<LightInstanceCount>,
+) {
+    light_instances.clear();
+
+    light_instance_count.0 = extracted_instances.instances.len() as u32;
+    for instance in extracted_instances.instances.drain(..) {
+        light_instances.push(instance);
+    }
+    light_instances.write_buffer(&render_device, &render_queue);
+}
@@ -1,6 +1,17 @@
 #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
 #import bevy_render::view::View
-#import bevy_light_2d::types::{AmbientLight2d, PointLight2d, PointLightMeta, SpotLight2d, SpotLightMeta}
+#import bevy_light_2d::types::{
+    AMBIENT_MAPPING_WORLD,
+    AMBIENT_ZONE_CIRCLE,
+    AMBIENT_ZONE_POLYGON,
+    AmbientLight2d,
+    AmbientZone2d,
+    LightMapSettings,
+    PointLight2d,
+    PointLightMeta,
+    SpotLight2d,
+    SpotLightMeta
+}
 #import bevy_light_2d::view_transformations::{
     frag_coord_to_ndc,
     ndc_to_world,
@@ -11,8 +22,8 @@
 // We're currently only using a single uniform binding for point lights in
 // WebGL2, which is limited to 4kb in BatchedUniformBuffer, so we need to
 // ensure our point lights can fit in 4kb.
-const MAX_POINT_LIGHTS: u32 = 82u;
-const MAX_SPOT_LIGHTS:  u32 = 64u;
+const MAX_POINT_LIGHTS: u32 = 64u;
+const MAX_SPOT_LIGHTS:  u32 = 51u;
 
 @group(0) @binding(0)
 var<uniform> view: View;
@@ -51,27 +62,109 @@ var sdf_sampler: sampler;
 @group(0) @binding(7)
 var<uniform> spot_light_meta: SpotLightMeta;
 
+// Light from transient lights and emitter batches, drawn ahead of the light map.
+@group(0) @binding(8)
+var light_instances: texture_2d<f32>;
+
+@group(0) @binding(9)
+var emissive: texture_2d<f32>;
+
+@group(0) @binding(10)
+var emissive_sampler: sampler;
+
+@group(0) @binding(11)
+var<uniform> settings: LightMapSettings;
+
+// The emission of the nearest occluder, with the distance its glow reaches in
+// alpha.
+@group(0) @binding(12)
+var occluder_emission: texture_2d<f32>;
+
+@group(0) @binding(13)
+var normals: texture_2d<f32>;
+
+// Specular intensity (r) and gloss (g) of specular sprites.
+@group(0) @binding(14)
+var specular_params: texture_2d<f32>;
+
+// The density of fog volumes.
+@group(0) @binding(15)
+var fog_volumes: texture_2d<f32>;
+
+// A texture tiled across the world, adding to the density of fog.
+@group(0) @binding(16)
+var fog_texture: texture_2d<f32>;
+
+// Also used for the ambient texture, which tiles the same way.
+@group(0) @binding(17)
+var fog_sampler: sampler;
+
+// A texture the ambient light is sampled from.
+@group(0) @binding(18)
+var ambient_texture: texture_2d<f32>;
+
+// The size of a screen texel, in world units.
+var<private> texel_size: f32;
+
+struct LightMapOutput {
+    @location(0) light: vec4<f32>,
+    @location(1) specular: vec4<f32>,
+}
+
 @fragment
-fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+fn fragment(in: FullscreenVertexOutput) -> LightMapOutput {
     let pos = ndc_to_world(frag_coord_to_ndc(in.position.xy));
 
-    if get_distance(pos) <= 0.0 {
-        return vec4(ambient_light.color.rgb, 1.0);
+    // The size of a screen texel, in world units.
+    texel_size = max(length(dpdx(pos)), 1e-4);
+
+    // Emissive sprites light themselves, even when drawn over an occluder.
+    let emission = get_emission(pos) + get_occluder_emission(pos);
+
+    // Fog hides the scene behind it, and scatters light gathered along the
+    // rays towards each light.
+    let fog_density = get_fog_density(pos);
+    let transmittance = exp(-fog_density);
+    var scattered = vec3(0.0);
+    let fog_jitter = interleaved_gradient_noise(in.position.xy);
+
+    // Halos are drawn over everything, including occluders.
+    let halo = get_halos(pos);
+
+    let ambient = get_ambient(pos);
+
+    // Light only reaches a little way into occluders (if at all), unless the
+    // occluder is short enough for lights to shine down on it.
+    let dist = get_distance(pos);
+    if dist <= 0.0
+        && -dist >= get_light_penetration(pos)
+        && get_occluder_height(pos) >= INFINITE_HEIGHT {
+        return LightMapOutput(vec4(ambient + emission, 1.0), vec4(halo, transmittance));
     }
 
-    var lighting_color = ambient_light.color.rgb;
-    
+    var lighting_color = ambient * get_ambient_occlusion(pos) + emission;
+    let normal = get_normal(pos);
+    let specular = get_specular(pos);
+    var specular_color = vec3(0.0);
+
     // Point lights
     for (var i = 0u; i < point_light_meta.count; i++) {
         let light = point_lights[i];
         let dist = distance(light.center, pos);
 
         if dist < light.radius {
-            let raymarch = raymarch(pos, light.center);
-
-            if raymarch > 0.0 || light.cast_shadows == 0 {
-                lighting_color += light.color.rgb * attenuation(dist, light.radius, light.intensity, light.falloff);
+            var visibility = 1.0;
+            if light.reflections != 0u {
+                visibility = raymarch_reflected(pos, light.center, light.height, light.reflections);
+            } else if light.cast_shadows != 0 {
+                visibility = raymarch(pos, light.center, light.height);
             }
+
+            let diffuse = diffuse(normal, pos, light.center, light.height);
+            let light_color = light.color.rgb * attenuation(dist, light.radius, light.intensity, light.falloff) * visibility;
+            lighting_color += light_color * diffuse;
+            specular_color += light_color * blinn_phong(normal, specular, pos, light.center, light.height);
+            scattered += light_color * ray_fog(pos, light.center, fog_jitter);
         }
     }
 
@@ -83,15 +176,30 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
         if dist < light.radius {
             let mask = spot_mask(light, pos, effective_center);
             if mask > 0.0 {
-                let vis = raymarch(pos, effective_center);
-                if vis > 0.0 || light.cast_shadows == 0u {
-                    lighting_color += light.color.rgb * attenuation(dist, light.radius, light.intensity, light.falloff) * mask;
+                var visibility = 1.0;
+                if light.reflections != 0u {
+                    visibility = raymarch_reflected(pos, effective_center, light.height, light.reflections);
+                } else if light.cast_shadows != 0u {
+                    visibility = raymarch(pos, effective_center, light.height);
                 }
+
+                let diffuse = diffuse(normal, pos, effective_center, light.height);
+                let light_color = light.color.rgb * attenuation(dist, light.radius, light.intensity, light.falloff) * mask * visibility;
+                lighting_color += light_color * diffuse;
+                specular_color += light_color * blinn_phong(normal, specular, pos, effective_center, light.height);
+                scattered += light_color * ray_fog(pos, effective_center, fog_jitter);
             }
         }
     }
 
-    return vec4(lighting_color, 1.0);
+    // Light instances don't cast shadows, and were already drawn over just the
+    // pixels they light.
+    let instance_light = textureLoad(light_instances, vec2<i32>(in.position.xy), 0).rgb;
+    lighting_color += instance_light;
+    scattered += instance_light * (1.0 - transmittance);
+
+    scattered *= settings.fog_color.rgb;
+    return LightMapOutput(vec4(lighting_color, 1.0), vec4(specular_color + scattered + halo, transmittance));
 }
 
 fn square(x: f32) -> f32 {
@@ -115,36 +223,502 @@ fn get_distance(pos: vec2<f32>) -> f32 {
     return dist;
 }
 
-fn distance_squared(a: vec2<f32>, b: vec2<f32>) -> f32 {
-    let c = a - b;
-    return dot(c, c);
+fn get_light_penetration(pos: vec2<f32>) -> f32 {
+    let uv = ndc_to_uv(world_to_ndc(pos));
+    return textureSampleLevel(sdf, sdf_sampler, uv, 0.0).g;
 }
 
-fn raymarch(ray_origin: vec2<f32>, ray_target: vec2<f32>) -> f32 {
-    let ray_direction = normalize(ray_target - ray_origin);
-    let stop_at = distance_squared(ray_origin, ray_target);
+// The largest value a 16 bit float can hold. Occluders are infinitely tall by
+// default, which is clamped to this when extracted. Keep this in sync with `extract.rs`.
+const INFINITE_HEIGHT: f32 = 65504.0;
+
+fn get_occluder_height(pos: vec2<f32>) -> f32 {
+    let uv = ndc_to_uv(world_to_ndc(pos));
+    return textureSampleLevel(sdf, sdf_sampler, uv, 0.0).b;
+}
+
+// The reflectivity of the nearest occluder.
+fn get_reflectivity(pos: vec2<f32>) -> f32 {
+    let uv = ndc_to_uv(world_to_ndc(pos));
+    return textureSampleLevel(sdf, sdf_sampler, uv, 0.0).a;
+}
+
+// The direction away from the nearest occluder's surface, from the gradient of
+// the SDF.
+fn get_surface_normal(pos: vec2<f32>) -> vec2<f32> {
+    let offset = vec2(texel_size, 0.0);
+    let gradient = vec2(
+        get_distance(pos + offset.xy) - get_distance(pos - offset.xy),
+        get_distance(pos + offset.yx) - get_distance(pos - offset.yx),
+    );
+
+    if all(gradient == vec2(0.0)) {
+        return vec2(0.0);
+    }
+
+    return normalize(gradient);
+}
+
+// Glows around the centers of lights, hidden when the center is inside an
+// occluder on screen.
+fn get_halos(pos: vec2<f32>) -> vec3<f32> {
+    var halo = vec3(0.0);
+
+    for (var i = 0u; i < point_light_meta.count; i++) {
+        let light = point_lights[i];
+        halo += light.color.rgb * halo_glow(pos, light.center, light.halo_intensity, light.halo_size);
+    }
+
+    for (var i = 0u; i < spot_light_meta.count; i++) {
+        let light = spot_lights[i];
+        halo += light.color.rgb * halo_glow(pos, light.center, light.halo_intensity, light.halo_size);
+    }
+
+    return halo;
+}
+
+fn halo_glow(pos: vec2<f32>, center: vec2<f32>, intensity: f32, size: f32) -> f32 {
+    if intensity <= 0.0 || size <= 0.0 {
+        return 0.0;
+    }
+
+    let dist = distance(pos, center);
+    if dist >= size {
+        return 0.0;
+    }
+
+    // Fade the halo out as the center sinks into an occluder, rather than
+    // popping. Off screen, the SDF would be clamped to whatever is at the edge,
+    // so the center is treated as unoccluded.
+    var occlusion = 1.0;
+    let center_uv = ndc_to_uv(world_to_ndc(center));
+    if all(center_uv >= vec2(0.0)) && all(center_uv <= vec2(1.0)) {
+        occlusion = smoothstep(-texel_size, texel_size, get_distance(center));
+    }
+    return intensity * square(1.0 - dist / size) * occlusion;
+}
+
+// Returns the surface normal (xyz) and how strongly it applies (w).
+fn get_normal(pos: vec2<f32>) -> vec4<f32> {
+    let uv = ndc_to_uv(world_to_ndc(pos));
+    let sampled = textureSampleLevel(normals, emissive_sampler, uv, 0.0);
+    if sampled.a <= 0.0 {
+        return vec4(0.0, 0.0, 1.0, 0.0);
+    }
+
+    // Sprites are blended by their alpha, which is undone before decoding.
+    let xy = (sampled.rg / sampled.a) * 2.0 - 1.0;
+    let z = sqrt(max(1.0 - dot(xy, xy), 0.0));
+    let strength = clamp(sampled.b / sampled.a, 0.0, 1.0) * sampled.a;
+    return vec4(xy, z, strength);
+}
+
+// Lambertian falloff for a light at the given position and height. Pixels
+// without a normal map are lit as flat sprites always have been.
+fn diffuse(normal: vec4<f32>, pos: vec2<f32>, light_center: vec2<f32>, light_height: f32) -> f32 {
+    if normal.w <= 0.0 {
+        return 1.0;
+    }
+
+    let to_light = vec3(light_center - pos, light_height);
+    if dot(to_light, to_light) <= 0.0 {
+        return mix(1.0, normal.z, normal.w);
+    }
+
+    let n_dot_l = max(dot(normal.xyz, normalize(to_light)), 0.0);
+    return mix(1.0, n_dot_l, normal.w);
+}
+
+// Returns the specular intensity (x) and gloss (y).
+fn get_specular(pos: vec2<f32>) -> vec2<f32> {
+    let uv = ndc_to_uv(world_to_ndc(pos));
+    let sampled = textureSampleLevel(specular_params, emissive_sampler, uv, 0.0);
+    if sampled.a <= 0.0 {
+        return vec2(0.0, 1.0);
+    }
+
+    // Sprites are blended by their alpha, which is undone for the gloss.
+    return vec2(sampled.r, max(sampled.g / sampled.a, 1.0));
+}
+
+// Blinn-Phong highlights for a light at the given position and height, seen
+// from straight above.
+fn blinn_phong(normal: vec4<f32>, specular: vec2<f32>, pos: vec2<f32>, light_center: vec2<f32>, light_height: f32) -> f32 {
+    if specular.x <= 0.0 {
+        return 0.0;
+    }
+
+    let to_light = vec3(light_center - pos, light_height);
+    if dot(to_light, to_light) <= 0.0 {
+        return 0.0;
+    }
+
+    let surface_normal = normalize(mix(vec3(0.0, 0.0, 1.0), normal.xyz, normal.w));
+    let half_vector = normalize(normalize(to_light) + vec3(0.0, 0.0, 1.0));
+    return specular.x * pow(max(dot(surface_normal, half_vector), 0.0), specular.y);
+}
+
+// Sums the density of the camera's fog, its texture and any fog volumes.
+fn get_fog_density(pos: vec2<f32>) -> f32 {
+    var density = settings.fog_density;
+
+    if settings.fog_texture_density > 0.0 {
+        let fog_uv = (pos - settings.fog_texture_offset) / settings.fog_texture_size;
+        density += textureSampleLevel(fog_texture, fog_sampler, fog_uv, 0.0).r * settings.fog_texture_density;
+    }
+
+    let uv = ndc_to_uv(world_to_ndc(pos));
+    density += textureSampleLevel(fog_volumes, emissive_sampler, uv, 0.0).r;
+
+    return density;
+}
+
+// The number of fog samples taken along each ray from a pixel to a light.
+const FOG_RAY_SAMPLES: u32 = 12u;
+
+// How much of a light's color is scattered towards a pixel by the fog along
+// the ray between them, from `0.0` to `1.0`.
+//
+// Light scattered anywhere along the ray travels the same distance to reach
+// the pixel, so it's attenuated (and shadowed) the same as light reaching the
+// pixel directly, and only the fog along the ray varies. Dense fog between a
+// light and a pixel streaks the pixel with light even where its own fog is
+// thin, drawing shafts outwards from lit fog, which occluders cut off along
+// with the light itself.
+fn ray_fog(pos: vec2<f32>, light_center: vec2<f32>, jitter: f32) -> f32 {
+    if settings.fog_enabled == 0u {
+        return 0.0;
+    }
+
+    var density = 0.0;
+    for (var i = 0u; i < FOG_RAY_SAMPLES; i++) {
+        let progress = (f32(i) + jitter) / f32(FOG_RAY_SAMPLES);
+        density += get_fog_density(mix(pos, light_center, progress));
+    }
+
+    return 1.0 - exp(-density / f32(FOG_RAY_SAMPLES));
+}
+
+// Jimenez 2014, "Next Generation Post Processing in Call of Duty: Advanced
+// Warfare".
+fn interleaved_gradient_noise(frag_coord: vec2<f32>) -> f32 {
+    return fract(52.9829189 * fract(dot(frag_coord, vec2(0.06711056, 0.00583715))));
+}
+
+const EMISSIVE_BLUR_SAMPLES: u32 = 16u;
+const GOLDEN_ANGLE: f32 = 2.39996323;
+
+fn sample_emissive(pos: vec2<f32>) -> vec3<f32> {
+    let uv = ndc_to_uv(world_to_ndc(pos));
+    return textureSampleLevel(emissive, emissive_sampler, uv, 0.0).rgb;
+}
+
+// Samples the emissive buffer, spread over a disk the size of the blur radius
+// so emission glows beyond the sprites it was drawn from.
+fn get_emission(pos: vec2<f32>) -> vec3<f32> {
+    if settings.emissive_blur <= 0.0 {
+        return sample_emissive(pos);
+    }
+
+    var emission = vec3(0.0);
+    var total_weight = 0.0;
+
+    for (var i = 0u; i < EMISSIVE_BLUR_SAMPLES; i++) {
+        // Spiral the samples outwards, weighting the center more heavily.
+        let t = (f32(i) + 0.5) / f32(EMISSIVE_BLUR_SAMPLES);
+        let r = sqrt(t) * settings.emissive_blur;
+        let theta = f32(i) * GOLDEN_ANGLE;
+        let weight = 1.0 - t;
+
+        emission += sample_emissive(pos + r * vec2(cos(theta), sin(theta))) * weight;
+        total_weight += weight;
+    }
+
+    return emission / total_weight;
+}
+
+// The ambient light at a position, blending in any ambient zones it's in (or
+// near) in order.
+fn get_ambient(pos: vec2<f32>) -> vec3<f32> {
+    var ambient = get_ambient_sources(pos);
+
+    for (var i = 0u; i < ambient_light.zone_count; i++) {
+        let zone = ambient_light.zones[i];
+        let dist = ambient_zone_sd(pos, zone);
+        let weight = 1.0 - smoothstep(0.0, max(zone.blend_distance, 1e-4), dist);
+        ambient = mix(ambient, zone.color.rgb, weight);
+    }
+
+    return ambient;
+}
+
+// The ambient light before zones are blended in, adding its uniform color,
+// texture and gradient.
+fn get_ambient_sources(pos: vec2<f32>) -> vec3<f32> {
+    var ambient = ambient_light.color.rgb;
+
+    if any(ambient_light.texture_tint.rgb > vec3(0.0)) {
+        let uv = ambient_uv(pos, ambient_light.texture_mapping, ambient_light.texture_rect);
+        let texel = textureSampleLevel(ambient_texture, fog_sampler, uv, 0.0).rgb;
+        ambient += texel * ambient_light.texture_tint.rgb;
+    }
+
+    // The gradient runs from the ground at the bottom to the sky at the top.
+    let gradient_uv = ambient_uv(pos, ambient_light.gradient_mapping, ambient_light.gradient_rect);
+    let height = saturate(1.0 - gradient_uv.y);
+    ambient += mix(ambient_light.gradient_ground.rgb, ambient_light.gradient_sky.rgb, height);
+
+    return ambient;
+}
+
+// Maps a world position to texture coordinates over the screen, or over a world
+// space rectangle given by its min and max corners.
+fn ambient_uv(pos: vec2<f32>, mapping: u32, rect: vec4<f32>) -> vec2<f32> {
+    if mapping == AMBIENT_MAPPING_WORLD {
+        return vec2(pos.x - rect.x, rect.w - pos.y) / (rect.zw - rect.xy);
+    }
+
+    return ndc_to_uv(world_to_ndc(pos));
+}
+
+fn ambient_zone_sd(pos: vec2<f32>, zone: AmbientZone2d) -> f32 {
+    if zone.shape == AMBIENT_ZONE_CIRCLE {
+        return distance(pos, zone.center) - zone.half_size.x;
+    }
+
+    if zone.shape == AMBIENT_ZONE_POLYGON {
+        return polygon_sd(pos, zone.vertex_start, zone.vertex_count);
+    }
+
+    let offset = pos - zone.center;
+    let local = vec2(dot(offset, zone.x_axis), dot(offset, vec2(-zone.x_axis.y, zone.x_axis.x)));
+    let d = abs(local) - zone.half_size;
+    return length(max(d, vec2(0.0))) + min(max(d.x, d.y), 0.0);
+}
+
+fn get_zone_vertex(index: u32) -> vec2<f32> {
+    let packed = ambient_light.zone_vertices[index / 2u];
+    return select(packed.xy, packed.zw, index % 2u == 1u);
+}
+
+// The signed distance to a polygon, which is negative inside it.
+// See https://iquilezles.org/articles/distfunctions2d/
+fn polygon_sd(pos: vec2<f32>, start: u32, count: u32) -> f32 {
+    let first = pos - get_zone_vertex(start);
+    var d = dot(first, first);
+    var s = 1.0;
+
+    for (var i = 0u; i < count; i++) {
+        let vi = get_zone_vertex(start + i);
+        let vj = get_zone_vertex(start + (i + count - 1u) % count);
+        let e = vj - vi;
+        let w = pos - vi;
+        let b = w - e * clamp(dot(w, e) / dot(e, e), 0.0, 1.0);
+        d = min(d, dot(b, b));
+
+        // Count the edges crossed by a ray from the position, to tell whether
+        // it's inside the polygon.
+        let c = vec3((pos.y >= vi.y), (pos.y < vj.y), (e.x * w.y > e.y * w.x));
+        if all(c) || !any(c) {
+            s = -s;
+        }
+    }
+
+    return s * sqrt(d);
+}
+
+const AMBIENT_OCCLUSION_SAMPLES: u32 = 8u;
+
+// Estimates how much of the area around a position is taken up by occluders,
+// by sampling the SDF at points spiralling out to the occlusion radius. Returns
+// how much ambient light reaches the position.
+fn get_ambient_occlusion(pos: vec2<f32>) -> f32 {
+    let radius = settings.ambient_occlusion_radius;
+    if settings.ambient_occlusion_strength <= 0.0 || radius <= 0.0 {
+        return 1.0;
+    }
+
+    var occlusion = 0.0;
+
+    for (var i = 0u; i < AMBIENT_OCCLUSION_SAMPLES; i++) {
+        let t = (f32(i) + 0.5) / f32(AMBIENT_OCCLUSION_SAMPLES);
+        let r = t * radius;
+        let theta = f32(i) * GOLDEN_ANGLE;
+
+        // A sample is occluded when it's inside an occluder, and partly
+        // occluded when one is closer to it than it is to the position.
+        let dist = get_distance(pos + r * vec2(cos(theta), sin(theta)));
+        occlusion += clamp(1.0 - dist / r, 0.0, 1.0);
+    }
+
+    occlusion /= f32(AMBIENT_OCCLUSION_SAMPLES);
+    return 1.0 - occlusion * settings.ambient_occlusion_strength;
+}
+
+// Glowing occluders light the area around them, fading out by their emission
+// distance from the surface.
+fn get_occluder_emission(pos: vec2<f32>) -> vec3<f32> {
+    let uv = ndc_to_uv(world_to_ndc(pos));
+    let emission = textureSampleLevel(occluder_emission, sdf_sampler, uv, 0.0);
+    let dist = get_distance(pos);
+
+    if dist <= 0.0 {
+        // The occluder's own surface glows at full strength.
+        return emission.rgb;
+    } else if dist < emission.a {
+        return emission.rgb * square(1.0 - dist / emission.a);
+    }
+
+    return vec3(0.0);
+}
+
+// Where a ray towards a light starts, as its progress, height and visibility.
+// Rays from the ground start at full visibility. Rays from inside an occluder
+// start on top of it when the light is higher, otherwise they need to find
+// their way out within the occluder's light penetration depth.
+fn ray_start(ray_origin: vec2<f32>, ray_direction: vec2<f32>, ray_length: f32, light_height: f32) -> vec3<f32> {
+    if get_distance(ray_origin) > 0.0 {
+        return vec3(0.0, 0.0, 1.0);
+    }
+
+    let occluder_height = get_occluder_height(ray_origin);
+    if occluder_height < light_height {
+        // The top of an occluder is lit by lights above it.
+        return vec3(0.0, occluder_height, 1.0);
+    }
+
+    let light_penetration = get_light_penetration(ray_origin);
+    if light_penetration <= 0.0 {
+        return vec3(0.0);
+    }
 
     var ray_progress: f32 = 0.0;
+
+    for (var i = 0; i < 16; i++) {
+        let dist = get_distance(ray_origin + ray_progress * ray_direction);
+        if dist > 0.0 || ray_progress >= ray_length {
+            break;
+        }
+
+        // Inside an occluder, the distance to its surface is a safe step size.
+        ray_progress += max(-dist, texel_size);
+    }
+
+    if ray_progress >= light_penetration {
+        return vec3(0.0);
+    }
+
+    return vec3(ray_progress, 0.0, 1.0 - ray_progress / light_penetration);
+}
+
+// Marches from a position on the ground towards a light, returning how much of
+// the light reaches it. Rays rise towards the light's height as they go, so
+// they can pass over occluders that are shorter than the ray at that point.
+fn raymarch(ray_origin: vec2<f32>, ray_target: vec2<f32>, light_height: f32) -> f32 {
+    let ray_direction = normalize(ray_target - ray_origin);
+    let ray_length = distance(ray_origin, ray_target);
+
+    let start = ray_start(ray_origin, ray_direction, ray_length, light_height);
+    let ray_start_height = start.y;
+    let visibility = start.z;
+    if visibility <= 0.0 {
+        return 0.0;
+    }
+
+    var ray_progress = start.x;
     var pos = vec2<f32>(0.0);
 
-    for (var i = 0; i < 32; i++) {
+    for (var i = 0; i < 48; i++) {
         pos = ray_origin + ray_progress * ray_direction;
 
-        if (ray_progress * ray_progress >= stop_at) {
+        if ray_progress >= ray_length {
             // ray found target
-            return 1.0;
+            return visibility;
         }
 
         let dist = get_distance(pos);
 
         if dist <= 0.0 {
-            break;
+            let ray_height = mix(ray_start_height, light_height, ray_progress / ray_length);
+
+            if ray_height < get_occluder_height(pos) {
+                // ray found occluder
+                return 0.0;
+            }
+
+            // The ray passes over the occluder, so step through it.
+            ray_progress += max(-dist, texel_size);
+        } else {
+            ray_progress += dist;
         }
+    }
+
+    // ray ran out of steps
+    return 0.0;
+}
 
-        ray_progress += dist;
+// Marches from a position towards the mirror image of a light, reflecting off
+// reflective occluders along the way. The light is only reached when the ray
+// reflects off as many occluders as the image was reflected in, which limits it
+// to the area the reflection really lights. Like `raymarch`, rays start within
+// an occluder's light penetration depth, and pass over occluders shorter than
+// them.
+fn raymarch_reflected(ray_origin: vec2<f32>, ray_target: vec2<f32>, light_height: f32, reflections: u32) -> f32 {
+    let ray_length = distance(ray_origin, ray_target);
+    var ray_direction = normalize(ray_target - ray_origin);
+
+    let start = ray_start(ray_origin, ray_direction, ray_length, light_height);
+    let ray_start_height = start.y;
+    let visibility = start.z;
+    if visibility <= 0.0 {
+        return 0.0;
+    }
+
+    var ray_progress = start.x;
+    var pos = ray_origin + ray_progress * ray_direction;
+    var reflected = 0u;
+
+    for (var i = 0; i < 64; i++) {
+        if ray_progress >= ray_length {
+            return select(0.0, visibility, reflected == reflections);
+        }
+
+        let dist = get_distance(pos);
+
+        if dist < texel_size {
+            let ray_height = mix(ray_start_height, light_height, ray_progress / ray_length);
+
+            if ray_height >= get_occluder_height(pos) {
+                // The ray passes over the occluder, so step through it.
+                let step = max(-dist, texel_size);
+                pos += ray_direction * step;
+                ray_progress += step;
+                continue;
+            }
+
+            if reflected == reflections || get_reflectivity(pos) <= 0.0 {
+                // ray found occluder
+                return 0.0;
+            }
+
+            let normal = get_surface_normal(pos);
+            if dot(ray_direction, normal) >= 0.0 {
+                return 0.0;
+            }
+
+            ray_direction = reflect(ray_direction, normal);
+            reflected += 1u;
+
+            // Step away from the surface, so it isn't hit again straight away.
+            pos += ray_direction * texel_size * 2.0;
+            ray_progress += texel_size * 2.0;
+        } else {
+            pos += ray_direction * dist;
+            ray_progress += dist;
+        }
     }
 
-    // ray found occluder
+    // ray ran out of steps
     return 0.0;
 }
 
@@ -22,6 +22,9 @@ pub const LIGHT_MAP_SHADER: Handle<Shader> = weak_handle!("48777bb3-8a37-4b4d-a4
 #[derive(Component)]
 pub struct LightMapTexture {
     pub light_map: CachedTexture,
+    /// Light added to the scene rather than multiplied with it, such as specular highlights and
+    /// light scattered by fog. The alpha channel holds how much of the scene shows through fog.
+    pub additive: CachedTexture,
 }
 
 #[derive(Resource, Default)]
@@ -1,19 +1,24 @@
 use bevy::prelude::*;
 use bevy::render::extract_component::{ComponentUniforms, DynamicUniformIndex};
 
+use bevy::render::render_asset::RenderAssets;
 use bevy::render::render_resource::{
     BindGroupEntries, GpuArrayBuffer, Operations, PipelineCache, RenderPassColorAttachment,
     RenderPassDescriptor,
 };
 use bevy::render::renderer::{RenderContext, RenderDevice, ViewQuery};
+use bevy::render::texture::{FallbackImage, FallbackImageZero, GpuImage};
 use bevy::render::view::{ViewUniformOffset, ViewUniforms};
 use smallvec::{SmallVec, smallvec};
 
 use crate::render::empty_buffer::EmptyBuffer;
 use crate::render::extract::{
-    ExtractedAmbientLight2d, ExtractedPointLight2d, ExtractedSpotLight2d,
+    ExtractedAmbientLight2d, ExtractedAmbientTexture, ExtractedFogTexture,
+    ExtractedLightMapSettings, ExtractedPointLight2d, ExtractedSpotLight2d,
 };
+use crate::render::light_instances::LightInstancesTexture;
 use crate::render::sdf::SdfTexture;
+use crate::render::sprite_layers::{SpriteLayer, SpriteLayerTextures};
 
 use super::{LightMapPipeline, LightMapTexture, PointLightMetaBuffer, SpotLightMetaBuffer};
 
@@ -24,13 +29,28 @@ pub fn light_map_pass(
     world: &World,
     view: ViewQuery<(
         &DynamicUniformIndex<ExtractedAmbientLight2d>,
+        &DynamicUniformIndex<ExtractedLightMapSettings>,
         &ViewUniformOffset,
         &LightMapTexture,
+        &LightInstancesTexture,
         &SdfTexture,
+        &SpriteLayerTextures,
+        Option<&ExtractedFogTexture>,
+        Option<&ExtractedAmbientTexture>,
     )>,
     mut ctx: RenderContext,
 ) {
-    let (ambient_index, view_offset, light_map_texture, sdf_texture) = view.into_inner();
+    let (
+        ambient_index,
+        settings_index,
+        view_offset,
+        light_map_texture,
+        light_instances_texture,
+        sdf_texture,
+        sprite_layer_textures,
+        extracted_fog_texture,
+        extracted_ambient_texture,
+    ) = view.into_inner();
 
     let light_map_pipeline = world.resource::<LightMapPipeline>();
     let pipeline_cache = world.resource::<PipelineCache>();
@@ -43,6 +63,7 @@ pub fn light_map_pass(
         Some(point_light_count_binding),
         Some(spot_light_binding),
         Some(spot_light_count_binding),
+        Some(settings_uniform),
     ) = (
         pipeline_cache.get_render_pipeline(light_map_pipeline.pipeline_id),
         world.resource::<ViewUniforms>().uniforms.binding(),
@@ -60,11 +81,31 @@ pub fn light_map_pass(
             .binding()
             .or(world.resource::<EmptyBuffer>().binding()),
         world.resource::<SpotLightMetaBuffer>().buffer.binding(),
+        world
+            .resource::<ComponentUniforms<ExtractedLightMapSettings>>()
+            .uniforms()
+            .binding(),
     )
     else {
         return;
     };
 
+    // Fog without a texture samples a blank one, as its texture density is zero.
+    let fallback_image = world.resource::<FallbackImage>();
+    let gpu_images = world.resource::<RenderAssets<GpuImage>>();
+    let fog_texture = extracted_fog_texture
+        .and_then(|fog_texture| gpu_images.get(fog_texture.0))
+        .map_or(&fallback_image.d2.texture_view, |gpu_image| {
+            &gpu_image.texture_view
+        });
+
+    // Likewise for ambient light without a texture, as its texture tint is black. A texture
+    // that's still loading adds no ambient light until it's ready.
+    let blank_image = &world.resource::<FallbackImageZero>().texture_view;
+    let ambient_texture = extracted_ambient_texture
+        .and_then(|ambient_texture| gpu_images.get(ambient_texture.0))
+        .map_or(blank_image, |gpu_image| &gpu_image.texture_view);
+
     let light_map_bind_group = ctx.render_device().create_bind_group(
         LIGHT_MAP_BIND_GROUP,
         &pipeline_cache.get_bind_group_layout(&light_map_pipeline.layout_descriptor),
@@ -77,37 +118,60 @@ pub fn light_map_pass(
             &light_map_pipeline.sdf_sampler,
             spot_light_binding.clone(),
             spot_light_count_binding.clone(),
+            &light_instances_texture.texture.default_view,
+            sprite_layer_textures.view(SpriteLayer::Emissive, blank_image),
+            &light_map_pipeline.emissive_sampler,
+            settings_uniform.clone(),
+            &sdf_texture.emission.default_view,
+            sprite_layer_textures.view(SpriteLayer::Normal, blank_image),
+            sprite_layer_textures.view(SpriteLayer::Specular, blank_image),
+            sprite_layer_textures.view(SpriteLayer::Fog, blank_image),
+            fog_texture,
+            &light_map_pipeline.fog_sampler,
+            ambient_texture,
         )),
     );
 
     let mut light_map_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
         label: Some(LIGHT_MAP_PASS),
-        color_attachments: &[Some(RenderPassColorAttachment {
-            view: &light_map_texture.light_map.default_view,
-            resolve_target: None,
-            ops: Operations::default(),
-            depth_slice: None,
-        })],
+        color_attachments: &[
+            Some(RenderPassColorAttachment {
+                view: &light_map_texture.light_map.default_view,
+                resolve_target: None,
+                ops: Operations::default(),
+                depth_slice: None,
+            }),
+            Some(RenderPassColorAttachment {
+                view: &light_map_texture.additive.default_view,
+                resolve_target: None,
+                ops: Operations::default(),
+                depth_slice: None,
+            }),
+        ],
         ..default()
     });
 
-    let mut light_map_offsets: SmallVec<[u32; 4]> =
+    let mut light_map_offsets: SmallVec<[u32; 7]> =
         smallvec![view_offset.offset, ambient_index.index()];
 
     // Storage buffers aren't available in WebGL2. We fall back to a
     // dynamic uniform buffer, and therefore need to provide the offset.
     // We're providing a value of 0 here as we're limiting the number of
     // point lights to only those that can reasonably fit in a single binding.
-    if world
+    // Offsets must be given in binding order.
+    let storage_buffers_unavailable = world
         .resource::<RenderDevice>()
         .limits()
         .max_storage_buffers_per_shader_stage
-        == 0
-    {
+        == 0;
+
+    if storage_buffers_unavailable {
         light_map_offsets.push(0); // point lights array
         light_map_offsets.push(0); // spot lights array
     }
 
+    light_map_offsets.push(settings_index.index());
+
     light_map_pass.set_render_pipeline(pipeline);
     light_map_pass.set_bind_group(0, &light_map_bind_group, &light_map_offsets);
     light_map_pass.draw(0..3, 0..1);
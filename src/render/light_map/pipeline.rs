@@ -1,18 +1,19 @@
 use bevy::core_pipeline::FullscreenShader;
 use bevy::ecs::resource::Resource;
 use bevy::ecs::world::{FromWorld, World};
+use bevy::prelude::default;
 use bevy::render::render_resource::binding_types::{sampler, texture_2d, uniform_buffer};
 use bevy::render::render_resource::{
-    BindGroupLayoutDescriptor, BindGroupLayoutEntries, CachedRenderPipelineId, ColorTargetState,
-    ColorWrites, FragmentState, GpuArrayBuffer, MultisampleState, PipelineCache, PrimitiveState,
-    RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages,
-    TextureFormat, TextureSampleType,
+    AddressMode, BindGroupLayoutDescriptor, BindGroupLayoutEntries, CachedRenderPipelineId,
+    ColorTargetState, ColorWrites, FilterMode, FragmentState, GpuArrayBuffer, MultisampleState,
+    PipelineCache, PrimitiveState, RenderPipelineDescriptor, Sampler, SamplerBindingType,
+    SamplerDescriptor, ShaderStages, TextureFormat, TextureSampleType,
 };
 use bevy::render::renderer::RenderDevice;
 use bevy::render::view::ViewUniform;
 
 use crate::render::extract::{
-    ExtractedAmbientLight2d, ExtractedPointLight2d, ExtractedSpotLight2d,
+    ExtractedAmbientLight2d, ExtractedLightMapSettings, ExtractedPointLight2d, ExtractedSpotLight2d,
 };
 
 use super::{LIGHT_MAP_SHADER, PointLightMeta, SpotLightMeta};
@@ -24,6 +25,8 @@ const LIGHT_MAP_PIPELINE: &str = "light_map_pipeline";
 pub struct LightMapPipeline {
     pub layout_descriptor: BindGroupLayoutDescriptor,
     pub sdf_sampler: Sampler,
+    pub emissive_sampler: Sampler,
+    pub fog_sampler: Sampler,
     pub pipeline_id: CachedRenderPipelineId,
 }
 
@@ -44,11 +47,35 @@ impl FromWorld for LightMapPipeline {
                     sampler(SamplerBindingType::Filtering),
                     GpuArrayBuffer::<ExtractedSpotLight2d>::binding_layout(limits),
                     uniform_buffer::<SpotLightMeta>(false),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    uniform_buffer::<ExtractedLightMapSettings>(true),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
                 ),
             ),
         );
 
         let sdf_sampler = render_device.create_sampler(&SamplerDescriptor::default());
+        let emissive_sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+        // Fog and ambient textures tile across the world.
+        let fog_sampler = render_device.create_sampler(&SamplerDescriptor {
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
         let fullscreen_shader = world.resource::<FullscreenShader>().clone();
         let pipeline_id =
             world
@@ -61,11 +88,20 @@ impl FromWorld for LightMapPipeline {
                         shader: LIGHT_MAP_SHADER,
                         shader_defs: vec![],
                         entry_point: Some("fragment".into()),
-                        targets: vec![Some(ColorTargetState {
-                            format: TextureFormat::Rgba16Float,
-                            blend: None,
-                            write_mask: ColorWrites::ALL,
-                        })],
+                        targets: vec![
+                            // Light map
+                            Some(ColorTargetState {
+                                format: TextureFormat::Rgba16Float,
+                                blend: None,
+                                write_mask: ColorWrites::ALL,
+                            }),
+                            // Specular
+                            Some(ColorTargetState {
+                                format: TextureFormat::Rgba16Float,
+                                blend: None,
+                                write_mask: ColorWrites::ALL,
+                            }),
+                        ],
                     }),
                     primitive: PrimitiveState::default(),
                     depth_stencil: None,
@@ -77,6 +113,8 @@ impl FromWorld for LightMapPipeline {
         Self {
             layout_descriptor,
             sdf_sampler,
+            emissive_sampler,
+            fog_sampler,
             pipeline_id,
         }
     }
@@ -18,6 +18,7 @@ use super::{
 };
 
 const LIGHT_MAP_TEXTURE: &str = "light_map_texture";
+const LIGHT_MAP_ADDITIVE_TEXTURE: &str = "light_map_additive_texture";
 
 pub fn prepare_light_map_texture(
     mut commands: Commands,
@@ -26,22 +27,25 @@ pub fn prepare_light_map_texture(
     view_targets: Query<(Entity, &ViewTarget)>,
 ) {
     for (entity, view_target) in &view_targets {
-        let light_map_texture = texture_cache.get(
-            &render_device,
-            TextureDescriptor {
-                label: Some(LIGHT_MAP_TEXTURE),
-                size: view_target.main_texture().size(),
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: TextureDimension::D2,
-                format: TextureFormat::Rgba16Float,
-                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            },
-        );
+        let mut light_map_texture = |label| {
+            texture_cache.get(
+                &render_device,
+                TextureDescriptor {
+                    label: Some(label),
+                    size: view_target.main_texture().size(),
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: TextureFormat::Rgba16Float,
+                    usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                },
+            )
+        };
 
         commands.entity(entity).insert(LightMapTexture {
-            light_map: light_map_texture,
+            light_map: light_map_texture(LIGHT_MAP_TEXTURE),
+            additive: light_map_texture(LIGHT_MAP_ADDITIVE_TEXTURE),
         });
     }
 }
@@ -1,4 +1,16 @@
 #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_light_2d::types::{
+    LIGHT_COMPOSITE_MULTIPLY_ADDITIVE,
+    LIGHT_COMPOSITE_OVERLAY,
+    LIGHT_COMPOSITE_SOFT_LIGHT,
+    LIGHT_GRADING_CURVE_SAMPLES,
+    LIGHT_GRADING_CURVES,
+    LIGHT_GRADING_LUT,
+    LightColorGrading2d,
+    LightComposite2d
+}
+
+const GAMMA: f32 = 2.2;
 
 @group(0) @binding(0)
 var screen_texture: texture_2d<f32>;
@@ -9,9 +21,126 @@ var light_map_texture: texture_2d<f32>;
 @group(0) @binding(2)
 var texture_sampler: sampler;
 
+@group(0) @binding(3)
+var unlit_texture: texture_2d<f32>;
+
+@group(0) @binding(4)
+var additive_texture: texture_2d<f32>;
+
+@group(0) @binding(5)
+var global_illumination_texture: texture_2d<f32>;
+
+@group(0) @binding(6)
+var global_illumination_sampler: sampler;
+
+// The exposure, in the red channel of a single texel. Zero until auto exposure has measured the
+// scene, such as while its pipeline is still compiling.
+@group(0) @binding(7)
+var exposure_texture: texture_2d<f32>;
+
+@group(0) @binding(8)
+var<uniform> composite: LightComposite2d;
+
+@group(0) @binding(9)
+var<uniform> color_grading: LightColorGrading2d;
+
+@group(0) @binding(10)
+var lut_texture: texture_3d<f32>;
+
+@group(0) @binding(11)
+var lut_sampler: sampler;
+
 @fragment
 fn fragment(vo: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    let light_frag = textureSample(light_map_texture, texture_sampler, vo.uv);
+    let auto_exposure = textureLoad(exposure_texture, vec2(0), 0).r;
+    let exposure = select(auto_exposure, 1.0, auto_exposure <= 0.0) * composite.exposure;
+
+    // Light bounced off the scene adds to the direct lighting.
+    let bounced = textureSample(global_illumination_texture, global_illumination_sampler, vo.uv);
+    let direct = textureSample(light_map_texture, texture_sampler, vo.uv);
+    let light_frag = vec4(grade_light((direct.rgb + bounced.rgb) * exposure), direct.a);
     let scene_frag = textureSample(screen_texture, texture_sampler, vo.uv);
-    return scene_frag * light_frag;
+
+    // Unlit sprites skip some (or all) of the light map.
+    let unlit = textureSample(unlit_texture, texture_sampler, vo.uv).r;
+    let light = mix(light_frag, vec4(1.0), unlit);
+    let lit = vec4(composite_light(scene_frag.rgb, light.rgb), scene_frag.a * light.a);
+
+    // Specular highlights and light scattered by fog are added on top, rather
+    // than tinted by the scene. Fog also hides the scene behind it.
+    let additive = textureSample(additive_texture, texture_sampler, vo.uv);
+    let transmittance = mix(additive.a, 1.0, unlit);
+    return vec4(lit.rgb * transmittance + additive.rgb * exposure * (1.0 - unlit), lit.a);
+}
+
+// Color grades the light up to 1.0, adding any light beyond that on top.
+fn grade_light(light: vec3<f32>) -> vec3<f32> {
+    let base = saturate(light);
+    let excess = light - base;
+
+    if color_grading.mode == LIGHT_GRADING_CURVES {
+        return vec3(
+            sample_curve(base.r, 0u),
+            sample_curve(base.g, 1u),
+            sample_curve(base.b, 2u),
+        ) + excess;
+    }
+
+    if color_grading.mode == LIGHT_GRADING_LUT {
+        // Sample the centers of the first and last entries at 0.0 and 1.0.
+        let size = f32(textureDimensions(lut_texture).x);
+        let uvw = (base * (size - 1.0) + 0.5) / size;
+        return textureSampleLevel(lut_texture, lut_sampler, uvw, 0.0).rgb + excess;
+    }
+
+    return light;
+}
+
+// Linearly interpolates one channel's baked curve.
+fn sample_curve(value: f32, channel: u32) -> f32 {
+    let position = value * f32(LIGHT_GRADING_CURVE_SAMPLES - 1u);
+    let index = min(u32(position), LIGHT_GRADING_CURVE_SAMPLES - 2u);
+    let a = color_grading.curves[index][channel];
+    let b = color_grading.curves[index + 1u][channel];
+    return mix(a, b, position - f32(index));
+}
+
+// Applies the light to the scene's color, using the camera's composite mode.
+fn composite_light(scene: vec3<f32>, light: vec3<f32>) -> vec3<f32> {
+    // In gamma space, the light is applied to the scene's sRGB colors as is.
+    var base = scene;
+    if composite.gamma != 0u {
+        base = pow(max(scene, vec3(0.0)), vec3(1.0 / GAMMA));
+    }
+
+    // The blend modes treat a light of 1.0 as the neutral middle of the blend layer.
+    let blend = min(light * 0.5, vec3(1.0));
+
+    var result: vec3<f32>;
+    if composite.mode == LIGHT_COMPOSITE_SOFT_LIGHT {
+        result = soft_light(base, blend);
+    } else if composite.mode == LIGHT_COMPOSITE_OVERLAY {
+        result = overlay(base, blend);
+    } else if composite.mode == LIGHT_COMPOSITE_MULTIPLY_ADDITIVE {
+        result = base * min(light, vec3(1.0)) + max(light - 1.0, vec3(0.0));
+    } else {
+        result = base * light;
+    }
+
+    if composite.gamma != 0u {
+        result = pow(max(result, vec3(0.0)), vec3(GAMMA));
+    }
+
+    return result;
+}
+
+// The "Pegtop" soft light formula, which is continuous unlike most others.
+fn soft_light(base: vec3<f32>, blend: vec3<f32>) -> vec3<f32> {
+    return (1.0 - 2.0 * blend) * base * base + 2.0 * blend * base;
+}
+
+fn overlay(base: vec3<f32>, blend: vec3<f32>) -> vec3<f32> {
+    let dark = 2.0 * base * blend;
+    let light = 1.0 - 2.0 * (1.0 - base) * (1.0 - blend);
+    return select(light, dark, base < vec3(0.5));
 }
@@ -1,12 +1,21 @@
 use bevy::prelude::*;
 
+use bevy::render::extract_component::{ComponentUniforms, DynamicUniformIndex};
+use bevy::render::render_asset::RenderAssets;
 use bevy::render::render_resource::{
     BindGroupEntries, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
 };
 use bevy::render::renderer::{RenderContext, ViewQuery};
+use bevy::render::texture::{FallbackImage, FallbackImageZero, GpuImage};
 use bevy::render::view::ViewTarget;
 
+use crate::render::auto_exposure::AutoExposureTextures;
+use crate::render::extract::{
+    ExtractedLightColorGrading2d, ExtractedLightComposite2d, ExtractedLightLut,
+};
+use crate::render::global_illumination::GlobalIlluminationTexture;
 use crate::render::light_map::LightMapTexture;
+use crate::render::sprite_layers::{SpriteLayer, SpriteLayerTextures};
 
 use super::{LightingPipeline, LightingPipelineId};
 
@@ -15,18 +24,67 @@ const LIGHTING_BIND_GROUP: &str = "lighting_bind_group";
 
 pub fn lighting_pass(
     world: &World,
-    view: ViewQuery<(&ViewTarget, &LightingPipelineId, &LightMapTexture)>,
+    view: ViewQuery<(
+        &ViewTarget,
+        &LightingPipelineId,
+        &DynamicUniformIndex<ExtractedLightComposite2d>,
+        &DynamicUniformIndex<ExtractedLightColorGrading2d>,
+        &LightMapTexture,
+        &SpriteLayerTextures,
+        Option<&GlobalIlluminationTexture>,
+        Option<&AutoExposureTextures>,
+        Option<&ExtractedLightLut>,
+    )>,
     mut ctx: RenderContext,
 ) {
-    let (view_target, pipeline_id, light_map_texture) = view.into_inner();
+    let (
+        view_target,
+        pipeline_id,
+        composite_index,
+        color_grading_index,
+        light_map_texture,
+        sprite_layer_textures,
+        global_illumination_texture,
+        auto_exposure_textures,
+        extracted_lut,
+    ) = view.into_inner();
 
     let pipeline = world.resource::<LightingPipeline>();
     let pipeline_cache = world.resource::<PipelineCache>();
 
-    let Some(lighting_pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+    let (Some(lighting_pipeline), Some(composite_binding), Some(color_grading_binding)) = (
+        pipeline_cache.get_render_pipeline(pipeline_id.0),
+        world
+            .resource::<ComponentUniforms<ExtractedLightComposite2d>>()
+            .uniforms()
+            .binding(),
+        world
+            .resource::<ComponentUniforms<ExtractedLightColorGrading2d>>()
+            .uniforms()
+            .binding(),
+    ) else {
         return;
     };
 
+    // Without global illumination, no bounced light is added.
+    let fallback_image = world.resource::<FallbackImageZero>();
+    let global_illumination = global_illumination_texture
+        .map_or(&fallback_image.texture_view, |global_illumination| {
+            &global_illumination.probes.default_view
+        });
+
+    // Without auto exposure, the exposure is read from a white texture.
+    let white_image = world.resource::<FallbackImage>();
+    let exposure =
+        auto_exposure_textures.map_or(&white_image.d2.texture_view, AutoExposureTextures::current);
+
+    // Without a lookup table, the color grading mode doesn't sample it.
+    let lut = extracted_lut
+        .and_then(|lut| world.resource::<RenderAssets<GpuImage>>().get(lut.0))
+        .map_or(&white_image.d3.texture_view, |gpu_image| {
+            &gpu_image.texture_view
+        });
+
     let post_process = view_target.post_process_write();
 
     let bind_group = ctx.render_device().create_bind_group(
@@ -36,6 +94,18 @@ pub fn lighting_pass(
             post_process.source,
             &light_map_texture.light_map.default_view,
             &pipeline.sampler,
+            sprite_layer_textures.view(
+                SpriteLayer::Unlit,
+                &world.resource::<FallbackImageZero>().texture_view,
+            ),
+            &light_map_texture.additive.default_view,
+            global_illumination,
+            &pipeline.global_illumination_sampler,
+            exposure,
+            composite_binding,
+            color_grading_binding,
+            lut,
+            &pipeline.lut_sampler,
         )),
     );
 
@@ -51,6 +121,10 @@ pub fn lighting_pass(
     });
 
     render_pass.set_render_pipeline(lighting_pipeline);
-    render_pass.set_bind_group(0, &bind_group, &[]);
+    render_pass.set_bind_group(
+        0,
+        &bind_group,
+        &[composite_index.index(), color_grading_index.index()],
+    );
     render_pass.draw(0..3, 0..1);
 }
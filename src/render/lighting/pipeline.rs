@@ -1,14 +1,18 @@
 use bevy::core_pipeline::FullscreenShader;
 use bevy::prelude::*;
-use bevy::render::render_resource::binding_types::{sampler, texture_2d};
+use bevy::render::render_resource::binding_types::{
+    sampler, texture_2d, texture_3d, uniform_buffer,
+};
 use bevy::render::render_resource::{
-    BindGroupLayoutDescriptor, BindGroupLayoutEntries, ColorTargetState, ColorWrites,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntries, ColorTargetState, ColorWrites, FilterMode,
     FragmentState, MultisampleState, PrimitiveState, RenderPipelineDescriptor, Sampler,
     SamplerBindingType, SamplerDescriptor, ShaderStages, SpecializedRenderPipeline,
     TextureSampleType,
 };
 use bevy::render::renderer::RenderDevice;
 
+use crate::render::extract::{ExtractedLightColorGrading2d, ExtractedLightComposite2d};
+
 use super::{LIGHTING_SHADER, LightingPipelineKey};
 
 const LIGHTING_PIPELINE: &str = "lighting_pipeline";
@@ -18,6 +22,8 @@ const LIGHTING_BIND_GROUP_LAYOUT: &str = "lighting_bind_group_layout";
 pub struct LightingPipeline {
     pub layout_descriptor: BindGroupLayoutDescriptor,
     pub sampler: Sampler,
+    pub global_illumination_sampler: Sampler,
+    pub lut_sampler: Sampler,
     pub fullscreen_shader: FullscreenShader,
 }
 
@@ -33,16 +39,41 @@ impl FromWorld for LightingPipeline {
                     texture_2d(TextureSampleType::Float { filterable: true }),
                     texture_2d(TextureSampleType::Float { filterable: true }),
                     sampler(SamplerBindingType::Filtering),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    uniform_buffer::<ExtractedLightComposite2d>(true),
+                    uniform_buffer::<ExtractedLightColorGrading2d>(true),
+                    texture_3d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
                 ),
             ),
         );
 
         let sampler = render_device.create_sampler(&SamplerDescriptor::default());
 
+        // Global illumination is gathered at a lower resolution, so it's smoothed when upscaled.
+        let global_illumination_sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        // Lookup tables are interpolated between their entries.
+        let lut_sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
         let fullscreen_shader = world.resource::<FullscreenShader>().clone();
         Self {
             layout_descriptor,
             sampler,
+            global_illumination_sampler,
+            lut_sampler,
             fullscreen_shader,
         }
     }
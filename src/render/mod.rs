@@ -3,11 +3,16 @@ use bevy::{
     prelude::Shader,
 };
 
+pub mod auto_exposure;
 pub mod empty_buffer;
 pub mod extract;
+pub mod global_illumination;
+pub mod lens_flare;
+pub mod light_instances;
 pub mod light_map;
 pub mod lighting;
 pub mod sdf;
+pub mod sprite_layers;
 
 pub const TYPES_SHADER: Handle<Shader> = weak_handle!("606bf813-c0cc-40c8-9fd6-ffcb6a5d66d8");
 
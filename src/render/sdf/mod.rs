@@ -15,6 +15,7 @@ use bevy::{
 
 pub use node::sdf_pass;
 pub use pipeline::SdfPipeline;
+pub use prepare::prepare_occluder_emissions;
 pub use prepare::prepare_occluder_meta;
 pub use prepare::prepare_sdf_texture;
 
@@ -23,6 +24,9 @@ pub const SDF_SHADER: Handle<Shader> = weak_handle!("16251728-6dd9-481e-95a7-7c2
 #[derive(Component)]
 pub struct SdfTexture {
     pub sdf: CachedTexture,
+    /// The emission of the nearest occluder, with its color in rgb and the distance its glow
+    /// reaches in alpha. Occluders that don't glow store zero.
+    pub emission: CachedTexture,
 }
 #[derive(Resource, Default)]
 pub struct OccluderMetaBuffer {
@@ -44,3 +48,24 @@ impl OccluderMeta {
         }
     }
 }
+
+#[derive(Resource, Default)]
+pub struct OccluderEmissionMetaBuffer {
+    pub buffer: UniformBuffer<OccluderEmissionMeta>,
+}
+
+#[derive(Default, ShaderType)]
+pub struct OccluderEmissionMeta {
+    pub count: u32,
+    // WebGL2 structs must be 16 byte aligned.
+    _padding: Vec3,
+}
+
+impl OccluderEmissionMeta {
+    pub fn new(count: u32) -> Self {
+        Self {
+            count,
+            _padding: Vec3::ZERO,
+        }
+    }
+}
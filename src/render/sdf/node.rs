@@ -9,10 +9,10 @@ use bevy::render::view::{ViewUniformOffset, ViewUniforms};
 use smallvec::{SmallVec, smallvec};
 
 use crate::render::empty_buffer::EmptyBuffer;
-use crate::render::extract::ExtractedLightOccluder2d;
+use crate::render::extract::{ExtractedLightOccluder2d, ExtractedOccluderEmission2d};
 
 use super::pipeline::SdfPipeline;
-use super::{OccluderMetaBuffer, SdfTexture};
+use super::{OccluderEmissionMetaBuffer, OccluderMetaBuffer, SdfTexture};
 
 const SDF_PASS: &str = "sdf_pass";
 const SDF_BIND_GROUP: &str = "sdf_bind_group";
@@ -32,6 +32,8 @@ pub fn sdf_pass(
         Some(view_uniform_binding),
         Some(light_occluders_binding),
         Some(occluder_meta_buffer),
+        Some(occluder_emission_binding),
+        Some(occluder_emission_count_binding),
     ) = (
         pipeline_cache.get_render_pipeline(sdf_pipeline.pipeline_id),
         world.resource::<ViewUniforms>().uniforms.binding(),
@@ -40,6 +42,14 @@ pub fn sdf_pass(
             .binding()
             .or(world.resource::<EmptyBuffer>().binding()),
         world.resource::<OccluderMetaBuffer>().buffer.binding(),
+        world
+            .resource::<GpuArrayBuffer<ExtractedOccluderEmission2d>>()
+            .binding()
+            .or(world.resource::<EmptyBuffer>().binding()),
+        world
+            .resource::<OccluderEmissionMetaBuffer>()
+            .buffer
+            .binding(),
     )
     else {
         return;
@@ -52,17 +62,27 @@ pub fn sdf_pass(
             view_uniform_binding.clone(),
             light_occluders_binding,
             occluder_meta_buffer,
+            occluder_emission_binding,
+            occluder_emission_count_binding,
         )),
     );
 
     let mut sdf_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
         label: Some(SDF_PASS),
-        color_attachments: &[Some(RenderPassColorAttachment {
-            view: &sdf_texture.sdf.default_view,
-            resolve_target: None,
-            ops: Operations::default(),
-            depth_slice: None,
-        })],
+        color_attachments: &[
+            Some(RenderPassColorAttachment {
+                view: &sdf_texture.sdf.default_view,
+                resolve_target: None,
+                ops: Operations::default(),
+                depth_slice: None,
+            }),
+            Some(RenderPassColorAttachment {
+                view: &sdf_texture.emission.default_view,
+                resolve_target: None,
+                ops: Operations::default(),
+                depth_slice: None,
+            }),
+        ],
         ..default()
     });
 
@@ -78,7 +98,8 @@ pub fn sdf_pass(
         .max_storage_buffers_per_shader_stage
         == 0
     {
-        dynamic_offsets.push(0);
+        dynamic_offsets.push(0); // occluders array
+        dynamic_offsets.push(0); // occluder emissions array
     }
 
     sdf_pass.set_render_pipeline(pipeline);
@@ -9,10 +9,9 @@ use bevy::render::render_resource::{
 use bevy::render::renderer::RenderDevice;
 use bevy::render::view::ViewUniform;
 
-use crate::render::extract::ExtractedLightOccluder2d;
-use crate::render::light_map::PointLightMeta;
+use crate::render::extract::{ExtractedLightOccluder2d, ExtractedOccluderEmission2d};
 
-use super::SDF_SHADER;
+use super::{OccluderEmissionMeta, OccluderMeta, SDF_SHADER};
 
 const SDF_PIPELINE: &str = "sdf_pipeline";
 const SDF_BIND_GROUP_LAYOUT: &str = "sdf_bind_group_layout";
@@ -34,7 +33,9 @@ impl FromWorld for SdfPipeline {
                 (
                     uniform_buffer::<ViewUniform>(true),
                     GpuArrayBuffer::<ExtractedLightOccluder2d>::binding_layout(limits),
-                    uniform_buffer::<PointLightMeta>(false),
+                    uniform_buffer::<OccluderMeta>(false),
+                    GpuArrayBuffer::<ExtractedOccluderEmission2d>::binding_layout(limits),
+                    uniform_buffer::<OccluderEmissionMeta>(false),
                 ),
             ),
         );
@@ -49,11 +50,20 @@ impl FromWorld for SdfPipeline {
                 shader: SDF_SHADER,
                 shader_defs: vec![],
                 entry_point: Some("fragment".into()),
-                targets: vec![Some(ColorTargetState {
-                    format: TextureFormat::Rgba16Float,
-                    blend: None,
-                    write_mask: ColorWrites::ALL,
-                })],
+                targets: vec![
+                    // SDF
+                    Some(ColorTargetState {
+                        format: TextureFormat::Rgba16Float,
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                    }),
+                    // Emission
+                    Some(ColorTargetState {
+                        format: TextureFormat::Rgba16Float,
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                    }),
+                ],
             }),
             primitive: PrimitiveState::default(),
             depth_stencil: None,
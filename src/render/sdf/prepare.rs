@@ -4,18 +4,25 @@ use bevy::{
         system::{Commands, Query, Res, ResMut},
     },
     render::{
-        render_resource::{TextureDescriptor, TextureDimension, TextureFormat, TextureUsages},
+        render_resource::{
+            GpuArrayBuffer, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
         renderer::{RenderDevice, RenderQueue},
         texture::TextureCache,
         view::ViewTarget,
     },
 };
 
-use crate::render::extract::ExtractedLightOccluder2d;
+use crate::render::extract::{
+    ExtractedLightOccluder2d, ExtractedOccluderEmission2d, ExtractedOccluderEmissions,
+};
 
-use super::{OccluderMeta, OccluderMetaBuffer, SdfTexture};
+use super::{
+    OccluderEmissionMeta, OccluderEmissionMetaBuffer, OccluderMeta, OccluderMetaBuffer, SdfTexture,
+};
 
 const SDF_TEXTURE: &str = "sdf_texture";
+const SDF_EMISSION_TEXTURE: &str = "sdf_emission_texture";
 
 pub fn prepare_sdf_texture(
     mut commands: Commands,
@@ -24,23 +31,26 @@ pub fn prepare_sdf_texture(
     view_targets: Query<(Entity, &ViewTarget)>,
 ) {
     for (entity, view_target) in &view_targets {
-        let sdf_texture = texture_cache.get(
-            &render_device,
-            TextureDescriptor {
-                label: Some(SDF_TEXTURE),
-                size: view_target.main_texture().size(),
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: TextureDimension::D2,
-                format: TextureFormat::Rgba16Float,
-                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            },
-        );
-
-        commands
-            .entity(entity)
-            .insert(SdfTexture { sdf: sdf_texture });
+        let mut sdf_texture = |label| {
+            texture_cache.get(
+                &render_device,
+                TextureDescriptor {
+                    label: Some(label),
+                    size: view_target.main_texture().size(),
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: TextureFormat::Rgba16Float,
+                    usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                },
+            )
+        };
+
+        commands.entity(entity).insert(SdfTexture {
+            sdf: sdf_texture(SDF_TEXTURE),
+            emission: sdf_texture(SDF_EMISSION_TEXTURE),
+        });
     }
 }
 
@@ -56,3 +66,26 @@ pub fn prepare_occluder_meta(
         .buffer
         .write_buffer(&render_device, &render_queue);
 }
+
+pub fn prepare_occluder_emissions(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut extracted_emissions: ResMut<ExtractedOccluderEmissions>,
+    mut occluder_emissions: ResMut<GpuArrayBuffer<ExtractedOccluderEmission2d>>,
+    mut occluder_emission_count: ResMut<OccluderEmissionMetaBuffer>,
+) {
+    occluder_emissions.clear();
+
+    let count = extracted_emissions.emissions.len() as u32;
+    for emission in extracted_emissions.emissions.drain(..) {
+        occluder_emissions.push(emission);
+    }
+    occluder_emissions.write_buffer(&render_device, &render_queue);
+
+    occluder_emission_count
+        .buffer
+        .set(OccluderEmissionMeta::new(count));
+    occluder_emission_count
+        .buffer
+        .write_buffer(&render_device, &render_queue);
+}
@@ -1,14 +1,20 @@
 #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
 #import bevy_render::view::View
-#import bevy_light_2d::types::{LightOccluder2d, OccluderMeta};
+#import bevy_light_2d::types::{
+    LightOccluder2d,
+    OccluderEmission2d,
+    OccluderEmissionMeta,
+    OccluderMeta
+};
 #import bevy_light_2d::view_transformations::{frag_coord_to_ndc, ndc_to_world};
 
 // We're currently only using a single uniform binding for occluders in
 // WebGL2, which is limited to 4kb in BatchedUniformBuffer, so we need to
 // ensure our occluders can fit in 4kb.
 //
-// As each occluder is 16 bytes, we can fit 4096 / 16 = 256 occluders.
-const MAX_OCCLUDERS: u32 = 256u;
+// As each occluder is padded to 32 bytes, we can fit 4096 / 32 = 128 occluders.
+const MAX_OCCLUDERS: u32 = 128u;
+const MAX_OCCLUDER_EMISSIONS: u32 = 64u;
 
 @group(0) @binding(0)
 var<uniform> view: View;
@@ -26,8 +32,25 @@ var<uniform> view: View;
 @group(0) @binding(2)
 var<uniform> occluder_meta: OccluderMeta;
 
+// Occluder emissions: SSBO on modern backends, UBO array on WebGL2
+#if AVAILABLE_STORAGE_BUFFER_BINDINGS >= 6
+    @group(0) @binding(3)
+    var<storage> occluder_emissions: array<OccluderEmission2d>;
+#else
+    @group(0) @binding(3)
+    var<uniform> occluder_emissions: array<OccluderEmission2d, MAX_OCCLUDER_EMISSIONS>;
+#endif
+
+@group(0) @binding(4)
+var<uniform> occluder_emission_meta: OccluderEmissionMeta;
+
+struct SdfOutput {
+    @location(0) sdf: vec4<f32>,
+    @location(1) emission: vec4<f32>,
+}
+
 @fragment
-fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+fn fragment(in: FullscreenVertexOutput) -> SdfOutput {
     let pos = ndc_to_world(frag_coord_to_ndc(in.position.xy));
 
     // WebGL2 does not support storage buffers (or runtime sized arrays), so we
@@ -40,16 +63,54 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
 
     // If there aren't any occluders, use the max value for the texture.
     if (occluder_count == 0) {
-        return vec4(255.0, 0.0, 0.0, 1.0);
+        return SdfOutput(vec4(255.0, 0.0, 0.0, 0.0), vec4(0.0));
     }
 
     var sdf = occluder_sd(pos, occluders[0]);
+    var light_penetration = 0.0;
+    var height = 0.0;
+    var reflectivity = occluders[0].reflectivity;
+
+    for (var i = 0u; i < occluder_count; i++) {
+        let occluder_sdf = occluder_sd(pos, occluders[i]);
+
+        // Surfaces reflect light as the nearest occluder does.
+        if occluder_sdf < sdf {
+            reflectivity = occluders[i].reflectivity;
+        }
+        sdf = min(sdf, occluder_sdf);
+
+        // Where occluders overlap, light reaches as far as the deepest one allows.
+        if occluder_sdf <= 0.0 {
+            light_penetration = max(light_penetration, occluders[i].light_penetration);
+            height = max(height, occluders[i].height);
+        }
+    }
+
+    return SdfOutput(vec4(sdf, light_penetration, height, reflectivity), get_emission(pos, sdf));
+}
+
+// The emission of the nearest occluder, if it glows. Other occluders in
+// between cut the glow off, as they're nearer.
+fn get_emission(pos: vec2f, sdf: f32) -> vec4f {
+#if AVAILABLE_STORAGE_BUFFER_BINDINGS >= 6
+    let emission_count = occluder_emission_meta.count;
+#else
+    let emission_count = min(MAX_OCCLUDER_EMISSIONS, occluder_emission_meta.count);
+#endif
+
+    for (var i = 0u; i < emission_count; i++) {
+        let emission = occluder_emissions[i];
+        let local_pos = emission.center - pos;
+        let d = abs(local_pos) - emission.half_size;
+        let emission_sdf = length(max(d, vec2f(0.))) + min(max(d.x, d.y), 0.);
 
-    for (var i = 1u; i < occluder_count; i++) {
-        sdf = min(sdf, occluder_sd(pos, occluders[i]));
+        if emission_sdf <= sdf {
+            return vec4(emission.color.rgb, emission.distance);
+        }
     }
 
-    return vec4(sdf, 0.0, 0.0, 1.0);
+    return vec4(0.0);
 }
 
 fn occluder_sd(p: vec2f, occluder: LightOccluder2d) -> f32 {
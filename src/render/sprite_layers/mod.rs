@@ -0,0 +1,153 @@
+mod node;
+mod pipeline;
+mod prepare;
+
+use std::ops::Range;
+
+use bevy::{
+    asset::{AssetId, Handle, weak_handle},
+    camera::visibility::RenderLayers,
+    ecs::{component::Component, resource::Resource},
+    image::Image,
+    math::{Rect, Vec2, Vec4},
+    platform::collections::HashMap,
+    render::{
+        render_resource::{
+            BufferUsages, BufferVec, CachedRenderPipelineId, ShaderType, TextureView,
+        },
+        texture::CachedTexture,
+    },
+    shader::Shader,
+    transform::components::GlobalTransform,
+};
+
+pub use node::sprite_layers_pass;
+pub use pipeline::SpriteLayersPipeline;
+pub use prepare::{
+    prepare_sprite_layer_pipelines, prepare_sprite_layer_textures, prepare_sprite_layers,
+};
+
+pub const SPRITE_LAYERS_SHADER: Handle<Shader> =
+    weak_handle!("0b4a1c7e-5e0f-4a84-9a3c-3f7f3d1c6a52");
+
+/// A screen sized texture that sprites can be drawn into, to feed extra information into the
+/// light map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpriteLayer {
+    /// Light emitted by sprites, added to the light map.
+    Emissive,
+    /// How much each pixel ignores the light map, stored in the red channel.
+    Unlit,
+    /// World space surface normals in red and green, with blue holding the normal map's strength.
+    Normal,
+    /// Specular intensity in the red channel and gloss in the green channel.
+    Specular,
+    /// The density of fog volumes, added together in the red channel.
+    Fog,
+}
+
+impl SpriteLayer {
+    pub const ALL: [SpriteLayer; 5] = [
+        SpriteLayer::Emissive,
+        SpriteLayer::Unlit,
+        SpriteLayer::Normal,
+        SpriteLayer::Specular,
+        SpriteLayer::Fog,
+    ];
+}
+
+/// A view's sprite layers. Layers without any sprites this frame have no texture, and are read
+/// from a blank one instead.
+#[derive(Component)]
+pub struct SpriteLayerTextures {
+    pub emissive: Option<CachedTexture>,
+    pub unlit: Option<CachedTexture>,
+    pub normal: Option<CachedTexture>,
+    pub specular: Option<CachedTexture>,
+    pub fog: Option<CachedTexture>,
+}
+
+impl SpriteLayerTextures {
+    pub fn get(&self, layer: SpriteLayer) -> Option<&CachedTexture> {
+        match layer {
+            SpriteLayer::Emissive => self.emissive.as_ref(),
+            SpriteLayer::Unlit => self.unlit.as_ref(),
+            SpriteLayer::Normal => self.normal.as_ref(),
+            SpriteLayer::Specular => self.specular.as_ref(),
+            SpriteLayer::Fog => self.fog.as_ref(),
+        }
+    }
+
+    /// The layer's texture view, or `blank` if nothing was drawn into the layer. Every layer
+    /// reads as zero where no sprite covers it.
+    pub fn view<'a>(&'a self, layer: SpriteLayer, blank: &'a TextureView) -> &'a TextureView {
+        self.get(layer)
+            .map_or(blank, |texture| &texture.default_view)
+    }
+}
+
+/// A sprite to be drawn into one of the [`SpriteLayer`]s.
+pub struct ExtractedLayerSprite {
+    pub layer: SpriteLayer,
+    pub transform: GlobalTransform,
+    /// The sprite's own image, used to determine the sprite's size and shape.
+    pub sprite_image: AssetId<Image>,
+    /// The image sampled when drawing into the layer.
+    pub layer_image: AssetId<Image>,
+    pub color: Vec4,
+    pub anchor: Vec2,
+    pub rect: Option<Rect>,
+    pub custom_size: Option<Vec2>,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    /// Only views sharing one of these layers draw the sprite.
+    pub render_layers: RenderLayers,
+}
+
+/// Sprites extracted this frame, waiting to be drawn into their layers.
+#[derive(Resource, Default)]
+pub struct ExtractedSpriteLayers {
+    pub sprites: Vec<ExtractedLayerSprite>,
+}
+
+/// A sprite's quad, read as per-instance vertex attributes.
+#[derive(Default, Clone, ShaderType)]
+pub struct SpriteLayerInstance {
+    /// The world space axes of the sprite's quad, scaled by the quad's size.
+    pub x_axis: Vec2,
+    pub y_axis: Vec2,
+    /// The world space position of the quad's bottom left corner.
+    pub origin: Vec2,
+    pub uv_offset_scale: Vec4,
+    pub color: Vec4,
+}
+
+/// Consecutive sprites in a layer that share their images, drawn together.
+pub struct SpriteLayerBatch {
+    pub layer: SpriteLayer,
+    pub layer_image: AssetId<Image>,
+    pub sprite_image: AssetId<Image>,
+    pub instances: Range<u32>,
+}
+
+/// The sprites a view draws into its layers, back to front.
+#[derive(Component, Default)]
+pub struct ViewSpriteLayerBatches {
+    pub batches: Vec<SpriteLayerBatch>,
+}
+
+#[derive(Resource)]
+pub struct SpriteLayerMeta {
+    pub pipelines: HashMap<SpriteLayer, CachedRenderPipelineId>,
+    /// The instances of every view's batches.
+    pub instances: BufferVec<SpriteLayerInstance>,
+}
+
+impl Default for SpriteLayerMeta {
+    fn default() -> Self {
+        Self {
+            pipelines: HashMap::default(),
+            instances: BufferVec::new(BufferUsages::VERTEX),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_resource::{
+    BindGroup, BindGroupEntries, Operations, PipelineCache, RenderPassColorAttachment,
+    RenderPassDescriptor,
+};
+use bevy::render::renderer::{RenderContext, ViewQuery};
+use bevy::render::texture::GpuImage;
+use bevy::render::view::{ViewUniformOffset, ViewUniforms};
+
+use crate::render::extract::ExtractedAmbientLight2d;
+
+use super::{
+    SpriteLayer, SpriteLayerMeta, SpriteLayerTextures, SpriteLayersPipeline, ViewSpriteLayerBatches,
+};
+
+const SPRITE_LAYERS_PASS: &str = "sprite_layers_pass";
+const SPRITE_LAYERS_BIND_GROUP: &str = "sprite_layers_bind_group";
+const SPRITE_LAYERS_TEXTURE_BIND_GROUP: &str = "sprite_layers_texture_bind_group";
+
+pub fn sprite_layers_pass(
+    world: &World,
+    view: ViewQuery<
+        (
+            &ViewUniformOffset,
+            &SpriteLayerTextures,
+            &ViewSpriteLayerBatches,
+        ),
+        With<ExtractedAmbientLight2d>,
+    >,
+    mut ctx: RenderContext,
+) {
+    let (view_offset, sprite_layer_textures, view_batches) = view.into_inner();
+
+    let sprite_layers_pipeline = world.resource::<SpriteLayersPipeline>();
+    let pipeline_cache = world.resource::<PipelineCache>();
+    let meta = world.resource::<SpriteLayerMeta>();
+    let gpu_images = world.resource::<RenderAssets<GpuImage>>();
+
+    let bind_group =
+        world
+            .resource::<ViewUniforms>()
+            .uniforms
+            .binding()
+            .map(|view_uniform_binding| {
+                ctx.render_device().create_bind_group(
+                    SPRITE_LAYERS_BIND_GROUP,
+                    &pipeline_cache
+                        .get_bind_group_layout(&sprite_layers_pipeline.layout_descriptor),
+                    &BindGroupEntries::single(view_uniform_binding),
+                )
+            });
+
+    let mut texture_bind_groups: HashMap<(AssetId<Image>, AssetId<Image>), BindGroup> =
+        HashMap::default();
+    for batch in &view_batches.batches {
+        let (Some(layer_image), Some(sprite_image)) = (
+            gpu_images.get(batch.layer_image),
+            gpu_images.get(batch.sprite_image),
+        ) else {
+            continue;
+        };
+
+        texture_bind_groups
+            .entry((batch.layer_image, batch.sprite_image))
+            .or_insert_with(|| {
+                ctx.render_device().create_bind_group(
+                    SPRITE_LAYERS_TEXTURE_BIND_GROUP,
+                    &pipeline_cache
+                        .get_bind_group_layout(&sprite_layers_pipeline.texture_layout_descriptor),
+                    &BindGroupEntries::sequential((
+                        &layer_image.texture_view,
+                        &layer_image.sampler,
+                        &sprite_image.texture_view,
+                    )),
+                )
+            });
+    }
+
+    for layer in SpriteLayer::ALL {
+        // Layers without sprites have no texture, and are read from a blank one instead.
+        let Some(layer_texture) = sprite_layer_textures.get(layer) else {
+            continue;
+        };
+
+        let mut sprite_layers_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some(SPRITE_LAYERS_PASS),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &layer_texture.default_view,
+                resolve_target: None,
+                ops: Operations::default(),
+                depth_slice: None,
+            })],
+            ..default()
+        });
+
+        let (Some(bind_group), Some(instances), Some(pipeline)) = (
+            bind_group.as_ref(),
+            meta.instances.buffer(),
+            meta.pipelines
+                .get(&layer)
+                .and_then(|id| pipeline_cache.get_render_pipeline(*id)),
+        ) else {
+            continue;
+        };
+
+        sprite_layers_pass.set_render_pipeline(pipeline);
+        sprite_layers_pass.set_bind_group(0, bind_group, &[view_offset.offset]);
+        sprite_layers_pass.set_vertex_buffer(0, instances.slice(..));
+
+        for batch in view_batches
+            .batches
+            .iter()
+            .filter(|batch| batch.layer == layer)
+        {
+            let Some(texture_bind_group) =
+                texture_bind_groups.get(&(batch.layer_image, batch.sprite_image))
+            else {
+                continue;
+            };
+
+            sprite_layers_pass.set_bind_group(1, texture_bind_group, &[]);
+            sprite_layers_pass.draw(0..4, batch.instances.clone());
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use bevy::mesh::VertexBufferLayout;
+use bevy::prelude::*;
+use bevy::render::render_resource::binding_types::{sampler, texture_2d, uniform_buffer};
+use bevy::render::render_resource::{
+    BindGroupLayoutDescriptor, BindGroupLayoutEntries, BlendComponent, BlendFactor, BlendOperation,
+    BlendState, ColorTargetState, ColorWrites, FragmentState, MultisampleState, PrimitiveState,
+    PrimitiveTopology, RenderPipelineDescriptor, SamplerBindingType, ShaderStages, ShaderType,
+    SpecializedRenderPipeline, TextureFormat, TextureSampleType, VertexAttribute, VertexFormat,
+    VertexState, VertexStepMode,
+};
+use bevy::render::view::ViewUniform;
+
+use super::{SPRITE_LAYERS_SHADER, SpriteLayer, SpriteLayerInstance};
+
+const SPRITE_LAYERS_PIPELINE: &str = "sprite_layers_pipeline";
+const SPRITE_LAYERS_BIND_GROUP_LAYOUT: &str = "sprite_layers_bind_group_layout";
+const SPRITE_LAYERS_TEXTURE_BIND_GROUP_LAYOUT: &str = "sprite_layers_texture_bind_group_layout";
+
+pub const SPRITE_LAYER_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+#[derive(Resource)]
+pub struct SpriteLayersPipeline {
+    pub layout_descriptor: BindGroupLayoutDescriptor,
+    pub texture_layout_descriptor: BindGroupLayoutDescriptor,
+}
+
+impl FromWorld for SpriteLayersPipeline {
+    fn from_world(_world: &mut World) -> Self {
+        let layout_descriptor = BindGroupLayoutDescriptor::new(
+            SPRITE_LAYERS_BIND_GROUP_LAYOUT,
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::VERTEX_FRAGMENT,
+                (uniform_buffer::<ViewUniform>(true),),
+            ),
+        );
+
+        let texture_layout_descriptor = BindGroupLayoutDescriptor::new(
+            SPRITE_LAYERS_TEXTURE_BIND_GROUP_LAYOUT,
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                ),
+            ),
+        );
+
+        Self {
+            layout_descriptor,
+            texture_layout_descriptor,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for SpriteLayersPipeline {
+    type Key = SpriteLayer;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let (shader_def, blend) = match key {
+            // Emission from overlapping sprites adds up.
+            SpriteLayer::Emissive => (
+                "EMISSIVE",
+                BlendState {
+                    color: BlendComponent {
+                        src_factor: BlendFactor::SrcAlpha,
+                        dst_factor: BlendFactor::One,
+                        operation: BlendOperation::Add,
+                    },
+                    alpha: BlendComponent {
+                        src_factor: BlendFactor::One,
+                        dst_factor: BlendFactor::One,
+                        operation: BlendOperation::Add,
+                    },
+                },
+            ),
+            // Sprites cover the mask of those behind them.
+            SpriteLayer::Unlit => ("UNLIT", BlendState::ALPHA_BLENDING),
+            SpriteLayer::Normal => ("NORMAL", BlendState::ALPHA_BLENDING),
+            SpriteLayer::Specular => ("SPECULAR", BlendState::ALPHA_BLENDING),
+            // Overlapping fog volumes add up.
+            SpriteLayer::Fog => (
+                "FOG",
+                BlendState {
+                    color: BlendComponent {
+                        src_factor: BlendFactor::One,
+                        dst_factor: BlendFactor::One,
+                        operation: BlendOperation::Add,
+                    },
+                    alpha: BlendComponent::OVER,
+                },
+            ),
+        };
+
+        RenderPipelineDescriptor {
+            label: Some(SPRITE_LAYERS_PIPELINE.into()),
+            layout: vec![
+                self.layout_descriptor.clone(),
+                self.texture_layout_descriptor.clone(),
+            ],
+            vertex: VertexState {
+                shader: SPRITE_LAYERS_SHADER,
+                shader_defs: vec![shader_def.into()],
+                entry_point: Some("vertex".into()),
+                buffers: vec![instance_buffer_layout()],
+            },
+            fragment: Some(FragmentState {
+                shader: SPRITE_LAYERS_SHADER,
+                shader_defs: vec![shader_def.into()],
+                entry_point: Some("fragment".into()),
+                targets: vec![Some(ColorTargetState {
+                    format: SPRITE_LAYER_FORMAT,
+                    blend: Some(blend),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState {
+                topology: PrimitiveTopology::TriangleStrip,
+                ..default()
+            },
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            immediate_size: 0,
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+/// Reads each [`SpriteLayerInstance`] as it's laid out in the instance buffer, where the vectors
+/// are aligned to 16 bytes.
+fn instance_buffer_layout() -> VertexBufferLayout {
+    VertexBufferLayout {
+        array_stride: SpriteLayerInstance::min_size().get(),
+        step_mode: VertexStepMode::Instance,
+        attributes: vec![
+            // x_axis
+            VertexAttribute {
+                format: VertexFormat::Float32x2,
+                offset: 0,
+                shader_location: 0,
+            },
+            // y_axis
+            VertexAttribute {
+                format: VertexFormat::Float32x2,
+                offset: 8,
+                shader_location: 1,
+            },
+            // origin
+            VertexAttribute {
+                format: VertexFormat::Float32x2,
+                offset: 16,
+                shader_location: 2,
+            },
+            // uv_offset_scale
+            VertexAttribute {
+                format: VertexFormat::Float32x4,
+                offset: 32,
+                shader_location: 3,
+            },
+            // color
+            VertexAttribute {
+                format: VertexFormat::Float32x4,
+                offset: 48,
+                shader_location: 4,
+            },
+        ],
+    }
+}
@@ -0,0 +1,206 @@
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        entity::Entity,
+        query::With,
+        system::{Commands, Query, Res, ResMut},
+    },
+    math::{Affine3A, Quat, Vec2, Vec4},
+    render::{
+        render_asset::RenderAssets,
+        render_resource::{
+            PipelineCache, SpecializedRenderPipelines, TextureDescriptor, TextureDimension,
+            TextureUsages,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        texture::{GpuImage, TextureCache},
+        view::ViewTarget,
+    },
+};
+
+use crate::render::extract::ExtractedAmbientLight2d;
+
+use super::{
+    ExtractedSpriteLayers, SpriteLayer, SpriteLayerBatch, SpriteLayerInstance, SpriteLayerMeta,
+    SpriteLayerTextures, SpriteLayersPipeline, ViewSpriteLayerBatches,
+    pipeline::SPRITE_LAYER_FORMAT,
+};
+
+const EMISSIVE_TEXTURE: &str = "emissive_texture";
+const UNLIT_TEXTURE: &str = "unlit_texture";
+const NORMAL_TEXTURE: &str = "normal_texture";
+const SPECULAR_TEXTURE: &str = "specular_texture";
+const FOG_TEXTURE: &str = "fog_texture";
+
+pub fn prepare_sprite_layer_textures(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    extracted_sprites: Res<ExtractedSpriteLayers>,
+    view_targets: Query<
+        (Entity, &ViewTarget, Option<&RenderLayers>),
+        With<ExtractedAmbientLight2d>,
+    >,
+) {
+    for (entity, view_target, render_layers) in &view_targets {
+        let render_layers = render_layers.cloned().unwrap_or_default();
+
+        // Layers without sprites aren't worth allocating and clearing.
+        let mut layer_texture = |layer, label| {
+            extracted_sprites
+                .sprites
+                .iter()
+                .any(|sprite| {
+                    sprite.layer == layer && sprite.render_layers.intersects(&render_layers)
+                })
+                .then(|| {
+                    texture_cache.get(
+                        &render_device,
+                        TextureDescriptor {
+                            label: Some(label),
+                            size: view_target.main_texture().size(),
+                            mip_level_count: 1,
+                            sample_count: 1,
+                            dimension: TextureDimension::D2,
+                            format: SPRITE_LAYER_FORMAT,
+                            usage: TextureUsages::RENDER_ATTACHMENT
+                                | TextureUsages::TEXTURE_BINDING,
+                            view_formats: &[],
+                        },
+                    )
+                })
+        };
+
+        commands.entity(entity).insert(SpriteLayerTextures {
+            emissive: layer_texture(SpriteLayer::Emissive, EMISSIVE_TEXTURE),
+            unlit: layer_texture(SpriteLayer::Unlit, UNLIT_TEXTURE),
+            normal: layer_texture(SpriteLayer::Normal, NORMAL_TEXTURE),
+            specular: layer_texture(SpriteLayer::Specular, SPECULAR_TEXTURE),
+            fog: layer_texture(SpriteLayer::Fog, FOG_TEXTURE),
+        });
+    }
+}
+
+pub fn prepare_sprite_layer_pipelines(
+    pipeline_cache: Res<PipelineCache>,
+    sprite_layers_pipeline: Res<SpriteLayersPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<SpriteLayersPipeline>>,
+    mut meta: ResMut<SpriteLayerMeta>,
+) {
+    // Specialize up front, so the pipelines are compiled before the first sprite appears.
+    for layer in SpriteLayer::ALL {
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &sprite_layers_pipeline, layer);
+        meta.pipelines.insert(layer, pipeline_id);
+    }
+}
+
+pub fn prepare_sprite_layers(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
+    mut extracted_sprites: ResMut<ExtractedSpriteLayers>,
+    mut meta: ResMut<SpriteLayerMeta>,
+    views: Query<(Entity, Option<&RenderLayers>), With<ExtractedAmbientLight2d>>,
+) {
+    meta.instances.clear();
+
+    // Draw back to front, so sprites are layered the same way they are on screen.
+    extracted_sprites.sprites.sort_by(|a, b| {
+        a.transform
+            .translation()
+            .z
+            .total_cmp(&b.transform.translation().z)
+    });
+
+    let mut sprites = Vec::with_capacity(extracted_sprites.sprites.len());
+    for sprite in extracted_sprites.sprites.drain(..) {
+        let Some(sprite_image) = gpu_images.get(sprite.sprite_image) else {
+            continue;
+        };
+        if gpu_images.get(sprite.layer_image).is_none() {
+            continue;
+        }
+
+        let image_size = sprite_image.size_2d().as_vec2();
+
+        // Mirrors the way `bevy_sprite_render` maps a sprite onto its quad.
+        let mut uv_offset_scale = match sprite.rect {
+            Some(rect) => Vec4::new(
+                rect.min.x / image_size.x,
+                rect.max.y / image_size.y,
+                rect.width() / image_size.x,
+                -rect.height() / image_size.y,
+            ),
+            None => Vec4::new(0.0, 1.0, 1.0, -1.0),
+        };
+
+        if sprite.flip_x {
+            uv_offset_scale.x += uv_offset_scale.z;
+            uv_offset_scale.z *= -1.0;
+        }
+        if sprite.flip_y {
+            uv_offset_scale.y += uv_offset_scale.w;
+            uv_offset_scale.w *= -1.0;
+        }
+
+        let quad_size = sprite
+            .custom_size
+            .or(sprite.rect.map(|rect| rect.size()))
+            .unwrap_or(image_size);
+
+        let transform = sprite.transform.affine()
+            * Affine3A::from_scale_rotation_translation(
+                quad_size.extend(1.0),
+                Quat::IDENTITY,
+                (quad_size * (-sprite.anchor - Vec2::splat(0.5))).extend(0.0),
+            );
+
+        let instance = SpriteLayerInstance {
+            x_axis: transform.matrix3.x_axis.truncate(),
+            y_axis: transform.matrix3.y_axis.truncate(),
+            origin: transform.translation.truncate(),
+            uv_offset_scale,
+            color: sprite.color,
+        };
+
+        sprites.push((sprite, instance));
+    }
+
+    // Each view draws the sprites on its render layers, with consecutive sprites sharing images
+    // drawn in a single batch.
+    for (entity, render_layers) in &views {
+        let render_layers = render_layers.cloned().unwrap_or_default();
+        let mut batches: Vec<SpriteLayerBatch> = Vec::new();
+
+        for layer in SpriteLayer::ALL {
+            for (sprite, instance) in sprites.iter().filter(|(sprite, _)| {
+                sprite.layer == layer && sprite.render_layers.intersects(&render_layers)
+            }) {
+                let index = meta.instances.push(instance.clone()) as u32;
+
+                match batches.last_mut() {
+                    Some(batch)
+                        if batch.layer == layer
+                            && batch.layer_image == sprite.layer_image
+                            && batch.sprite_image == sprite.sprite_image =>
+                    {
+                        batch.instances.end = index + 1;
+                    }
+                    _ => batches.push(SpriteLayerBatch {
+                        layer,
+                        layer_image: sprite.layer_image,
+                        sprite_image: sprite.sprite_image,
+                        instances: index..index + 1,
+                    }),
+                }
+            }
+        }
+
+        commands
+            .entity(entity)
+            .insert(ViewSpriteLayerBatches { batches });
+    }
+
+    meta.instances.write_buffer(&render_device, &render_queue);
+}
@@ -0,0 +1,97 @@
+#import bevy_render::view::View
+
+@group(0) @binding(0)
+var<uniform> view: View;
+
+// The layer's own image, such as the emissive or normal map.
+@group(1) @binding(0)
+var layer_texture: texture_2d<f32>;
+
+@group(1) @binding(1)
+var layer_sampler: sampler;
+
+// The sprite's image, which gives the sprite its shape.
+@group(1) @binding(2)
+var sprite_texture: texture_2d<f32>;
+
+// A sprite's quad, read from the instance buffer.
+struct SpriteLayerInstance {
+    @location(0) x_axis: vec2<f32>,
+    @location(1) y_axis: vec2<f32>,
+    @location(2) origin: vec2<f32>,
+    @location(3) uv_offset_scale: vec4<f32>,
+    @location(4) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) @interpolate(flat) color: vec4<f32>,
+    @location(2) @interpolate(flat) x_axis: vec2<f32>,
+    @location(3) @interpolate(flat) y_axis: vec2<f32>,
+    @location(4) @interpolate(flat) uv_scale: vec2<f32>,
+};
+
+@vertex
+fn vertex(@builtin(vertex_index) index: u32, instance: SpriteLayerInstance) -> VertexOutput {
+    // Quad corners, drawn as a triangle strip.
+    let corner = vec2(f32(index & 1u), f32((index & 2u) >> 1u));
+    let world_position = instance.origin
+        + corner.x * instance.x_axis
+        + corner.y * instance.y_axis;
+
+    var out: VertexOutput;
+    out.position = view.clip_from_world * vec4(world_position, 0.0, 1.0);
+    out.uv = corner * instance.uv_offset_scale.zw + instance.uv_offset_scale.xy;
+    out.color = instance.color;
+    out.x_axis = instance.x_axis;
+    out.y_axis = instance.y_axis;
+    out.uv_scale = instance.uv_offset_scale.zw;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(layer_texture, layer_sampler, in.uv) * in.color;
+    let shape = textureSample(sprite_texture, layer_sampler, in.uv).a * in.color.a;
+
+#ifdef EMISSIVE
+    // Color is blended by alpha, and the intensity (which may exceed one) is
+    // carried in the color itself.
+    return vec4(color.rgb, clamp(color.a, 0.0, 1.0));
+#else ifdef UNLIT
+    // Only the sprite's shape matters, the mask value is carried in the color.
+    return vec4(in.color.r, 0.0, 0.0, shape);
+#else ifdef NORMAL
+    // Tangent space normal, with +Y pointing up the sprite. Flipped sprites
+    // have their UVs mirrored, which is undone here.
+    let sampled = textureSample(layer_texture, layer_sampler, in.uv).xyz * 2.0 - 1.0;
+    let tangent = vec3(
+        sampled.x * sign(in.uv_scale.x),
+        sampled.y * -sign(in.uv_scale.y),
+        sampled.z,
+    );
+
+    // Rotate into world space using the sprite's axes. The normal always faces
+    // the viewer, so only x and y are stored, leaving blue for how strongly
+    // the normal map applies (zero for sprites covering a normal mapped one).
+    let rotated = tangent.x * normalize(in.x_axis) + tangent.y * normalize(in.y_axis);
+    let normal = normalize(vec3(rotated, max(tangent.z, 0.0)));
+    return vec4(normal.xy * 0.5 + 0.5, in.color.r, shape);
+#else ifdef SPECULAR
+    // Intensity and gloss are carried in the color, with blue marking whether
+    // the intensity is scaled by a specular map.
+    let specular_map = textureSample(layer_texture, layer_sampler, in.uv).r;
+    let intensity = in.color.r * mix(1.0, specular_map, in.color.b);
+    return vec4(intensity, in.color.g, 0.0, shape);
+#else ifdef FOG
+    // Fog volumes fade in from their edges, over a fraction of their size
+    // carried in the green and blue channels.
+    let edge = min(in.uv, 1.0 - in.uv);
+    let falloff = max(in.color.gb, vec2(1e-5));
+    let fade = clamp(edge / falloff, vec2(0.0), vec2(1.0));
+    return vec4(in.color.r * fade.x * fade.y, 0.0, 0.0, 1.0);
+#else
+    return color;
+#endif
+}
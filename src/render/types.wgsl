@@ -1,12 +1,88 @@
 #define_import_path bevy_light_2d::types
 
+// WebGL2 does not support storage buffers, so ambient zones are stored in
+// fixed length arrays. Keep these in sync with `extract.rs`.
+const MAX_AMBIENT_ZONES: u32 = 16u;
+const MAX_AMBIENT_ZONE_VERTICES: u32 = 64u;
+
+const AMBIENT_MAPPING_SCREEN: u32 = 0u;
+const AMBIENT_MAPPING_WORLD: u32 = 1u;
+
+const AMBIENT_ZONE_RECTANGLE: u32 = 0u;
+const AMBIENT_ZONE_CIRCLE: u32 = 1u;
+const AMBIENT_ZONE_POLYGON: u32 = 2u;
+
+struct AmbientZone2d {
+    color: vec4<f32>,
+    center: vec2<f32>,
+    // The half size of a rectangle, or the radius of a circle in x.
+    half_size: vec2<f32>,
+    // The direction of a rectangle's local x axis, which rotates it.
+    x_axis: vec2<f32>,
+    shape: u32,
+    blend_distance: f32,
+    vertex_start: u32,
+    vertex_count: u32,
+    // WebGL2 uniform arrays need a 16 byte aligned stride.
+    _padding: vec2<f32>,
+}
+
 struct AmbientLight2d {
     color: vec4<f32>,
+    texture_tint: vec4<f32>,
+    // The world space rectangle the texture is mapped over, as its min and max
+    // corners.
+    texture_rect: vec4<f32>,
+    gradient_sky: vec4<f32>,
+    gradient_ground: vec4<f32>,
+    // The world space rectangle the gradient is mapped over, as its min and max
+    // corners.
+    gradient_rect: vec4<f32>,
+    texture_mapping: u32,
+    gradient_mapping: u32,
+    zone_count: u32,
+    zones: array<AmbientZone2d, MAX_AMBIENT_ZONES>,
+    // Polygon vertices, packed in pairs.
+    zone_vertices: array<vec4<f32>, MAX_AMBIENT_ZONE_VERTICES / 2u>,
+}
+
+struct LightMapSettings {
+    fog_color: vec4<f32>,
+    fog_texture_size: vec2<f32>,
+    fog_texture_offset: vec2<f32>,
+    emissive_blur: f32,
+    fog_density: f32,
+    fog_texture_density: f32,
+    ambient_occlusion_radius: f32,
+    ambient_occlusion_strength: f32,
+    // Whether there's any fog to gather along rays to lights.
+    fog_enabled: u32,
+    // WebGL2 structs must be 16 byte aligned.
+    _padding: vec2<f32>,
+}
+
+struct GlobalIllumination2d {
+    intensity: f32,
+    max_distance: f32,
+    ray_count: u32,
+    probe_spacing: u32,
 }
 
 struct LightOccluder2d {
     half_size: vec2<f32>,
     center: vec2<f32>,
+    light_penetration: f32,
+    height: f32,
+    reflectivity: f32,
+    // WebGL2 uniform arrays need a 16 byte aligned stride.
+    _padding: f32,
+}
+
+struct OccluderEmission2d {
+    half_size: vec2<f32>,
+    center: vec2<f32>,
+    color: vec4<f32>,
+    distance: f32,
 }
 
 struct PointLight2d {
@@ -15,7 +91,11 @@ struct PointLight2d {
     color: vec4<f32>,
     intensity: f32,
     falloff: f32,
-    cast_shadows: u32
+    cast_shadows: u32,
+    height: f32,
+    halo_intensity: f32,
+    halo_size: f32,
+    reflections: u32,
 }
 
 struct SpotLight2d {
@@ -29,6 +109,18 @@ struct SpotLight2d {
     outer_angle: f32,
     source_width: f32,
     cast_shadows: u32,
+    height: f32,
+    halo_intensity: f32,
+    halo_size: f32,
+    reflections: u32,
+}
+
+struct LightInstance2d {
+    center: vec2f,
+    radius: f32,
+    color: vec4<f32>,
+    intensity: f32,
+    falloff: f32,
 }
 
 struct PointLightMeta {
@@ -47,4 +139,56 @@ struct SpotLightMeta {
     count: u32,
     // WebGL2 structs must be 16 byte aligned.
     _padding: vec3<u32>,
-}
\ No newline at end of file
+}
+
+struct OccluderEmissionMeta {
+    count: u32,
+    // WebGL2 structs must be 16 byte aligned.
+    _padding: vec3<u32>,
+}
+
+struct LensFlareInstance {
+    light_center: vec2<f32>,
+    position: f32,
+    size: f32,
+    color: vec4<f32>,
+    textured: u32,
+    // WebGL2 structs must be 16 byte aligned.
+    _padding: vec3<u32>,
+}
+
+struct AutoExposure2d {
+    min_exposure: f32,
+    max_exposure: f32,
+    brighten_speed: f32,
+    darken_speed: f32,
+    delta_time: f32,
+    // WebGL2 structs must be 16 byte aligned.
+    _padding: vec3<f32>,
+}
+
+const LIGHT_COMPOSITE_MULTIPLY: u32 = 0u;
+const LIGHT_COMPOSITE_SOFT_LIGHT: u32 = 1u;
+const LIGHT_COMPOSITE_OVERLAY: u32 = 2u;
+const LIGHT_COMPOSITE_MULTIPLY_ADDITIVE: u32 = 3u;
+
+struct LightComposite2d {
+    mode: u32,
+    gamma: u32,
+    exposure: f32,
+    // WebGL2 structs must be 16 byte aligned.
+    _padding: f32,
+}
+
+const LIGHT_GRADING_CURVES: u32 = 1u;
+const LIGHT_GRADING_LUT: u32 = 2u;
+
+// Keep this in sync with `extract.rs`.
+const LIGHT_GRADING_CURVE_SAMPLES: u32 = 32u;
+
+struct LightColorGrading2d {
+    mode: u32,
+    // The red, green and blue curves, baked into evenly spaced samples from 0.0
+    // to 1.0.
+    curves: array<vec4<f32>, LIGHT_GRADING_CURVE_SAMPLES>,
+}
@@ -0,0 +1,113 @@
+//! A module which contains components that change how sprites interact with lighting.
+
+use bevy::{
+    asset::Handle,
+    ecs::component::Component,
+    image::Image,
+    prelude::{ReflectComponent, ReflectDefault},
+    reflect::Reflect,
+};
+
+/// Makes a `Sprite` emit light in its own shape, such as glowing eyes, lit windows or neon signs.
+///
+/// The sprite is drawn into an emissive buffer, which is added to the light map of every
+/// [`Light2d`](crate::light::Light2d) camera. Emission can be softened into a glow around the
+/// sprite with [`Light2d::emissive_blur`](crate::light::Light2d::emissive_blur).
+///
+/// Sprites using [`SpriteImageMode::Sliced`](bevy::sprite::SpriteImageMode::Sliced) or
+/// [`SpriteImageMode::Tiled`](bevy::sprite::SpriteImageMode::Tiled) are drawn as a single
+/// stretched quad.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct Emissive2d {
+    /// An optional emission map, laid out the same way as the sprite's image.
+    ///
+    /// When `None`, the sprite's own image (and color) is used, so the whole sprite glows.
+    pub image: Option<Handle<Image>>,
+    /// How brightly the sprite emits light.
+    pub intensity: f32,
+}
+
+impl Default for Emissive2d {
+    fn default() -> Self {
+        Self {
+            image: None,
+            intensity: 1.0,
+        }
+    }
+}
+
+/// Excludes a `Sprite` from lighting, such as damage numbers, interaction prompts or selection
+/// outlines that should stay readable in the dark.
+///
+/// Unlit sprites are written to a mask, which the lighting pass uses to skip (or blend) the
+/// light map for the pixels they cover. Sprites drawn in front of an unlit sprite are lit as usual.
+///
+/// The mask isn't written while the main 2D pass draws the scene, but in a sprite pass of its
+/// own afterwards. Every visible sprite in front of the lowest unlit sprite is drawn again into
+/// it, so unlit sprites near the front of the scene are the cheapest.
+///
+/// Only `Sprite`s are drawn into the mask, so meshes or text drawn in front of an unlit sprite
+/// are left unlit where they overlap it.
+#[derive(Component, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct Unlit2d {
+    /// How much the light map still affects the sprite, from `0.0` (fully unlit) to `1.0`
+    /// (fully lit).
+    pub lighting_weight: f32,
+}
+
+/// Gives a `Sprite` surface detail by lighting it with a normal map, such as bricks, rocks or
+/// the folds of a character's clothes.
+///
+/// The normal map is laid out the same way as the sprite's image, using the OpenGL convention
+/// (+Y pointing up the image). Normals are drawn into a screen space buffer and are rotated and
+/// flipped along with the sprite. Sprites without a normal map are lit as flat surfaces facing
+/// the camera.
+///
+/// Lights sitting on the ground only light surfaces facing them, so give lights a
+/// [`height`](crate::light::PointLight2d::height) above zero to light sprites from the front.
+///
+/// Normals are drawn in an extra sprite pass after the main 2D pass, which also redraws every
+/// visible sprite in front of the lowest normal mapped one.
+///
+/// Only `Sprite`s cover the normals of sprites behind them, so meshes drawn in front of a normal
+/// mapped sprite are lit with its normals where they overlap it.
+#[derive(Component, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct NormalMap2d(pub Handle<Image>);
+
+/// Adds specular highlights to a `Sprite`, such as the glint of metal props or wet floors.
+///
+/// Highlights are calculated for each point and spot light with the Blinn-Phong model, looking
+/// straight down at the scene. They're brightest where the surface faces halfway between the
+/// light and the camera, so combine this with [`NormalMap2d`] for detailed highlights, and give
+/// lights a [`height`](crate::light::PointLight2d::height) above zero.
+///
+/// Highlights are added on top of the lit scene, rather than multiplied with it. Only `Sprite`s
+/// cover the highlights of sprites behind them, so a mesh drawn in front of a specular sprite
+/// still shows its highlights.
+///
+/// Like [`NormalMap2d`], specular sprites are drawn in a separate pass after the main 2D pass,
+/// along with every visible sprite in front of the lowest specular sprite.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct Specular2d {
+    /// How bright the highlights are.
+    pub intensity: f32,
+    /// How shiny the surface is. Higher values give smaller, sharper highlights.
+    pub gloss: f32,
+    /// An optional specular map, laid out the same way as the sprite's image. Its red channel
+    /// scales [`Specular2d::intensity`] per pixel.
+    pub image: Option<Handle<Image>>,
+}
+
+impl Default for Specular2d {
+    fn default() -> Self {
+        Self {
+            intensity: 1.0,
+            gloss: 32.0,
+            image: None,
+        }
+    }
+}
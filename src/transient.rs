@@ -0,0 +1,111 @@
+//! A module which contains short-lived lights, such as muzzle flashes and explosions.
+
+use bevy::{
+    camera::visibility::Visibility,
+    color::Color,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        system::{Commands, EntityCommands, Query, Res},
+    },
+    math::{
+        Vec2,
+        curve::{Curve, EaseFunction, EasingCurve},
+    },
+    prelude::{ReflectComponent, ReflectDefault},
+    reflect::Reflect,
+    time::Time,
+    transform::components::Transform,
+};
+
+/// A light that lives for a short amount of time before despawning itself.
+///
+/// Transient lights are intended for effects like muzzle flashes, explosions and lightning, where
+/// many lights may exist at once. They don't cast shadows, and are uploaded to the GPU together in
+/// a single batch, rather than one at a time like a [`PointLight2d`](crate::light::PointLight2d).
+///
+/// The light's intensity and radius are multiplied by their respective curves, which are sampled
+/// over the light's lifetime (from `0.0` when spawned, to `1.0` when despawned).
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+#[require(Transform, Visibility)]
+pub struct TransientLight2d {
+    /// The light's color tint.
+    pub color: Color,
+    /// The intensity of the light. The light's attenutation is multiplied by this value.
+    pub intensity: f32,
+    /// The radius of the light. Illumination will only occur within the light's radius.
+    pub radius: f32,
+    /// How quickly illumination from the light should deteriorate over distance.
+    pub falloff: f32,
+    /// How long the light lives for, in seconds.
+    pub duration: f32,
+    /// How long the light has been alive for, in seconds. This is updated automatically.
+    pub elapsed: f32,
+    /// The curve the light's intensity is multiplied by over its lifetime.
+    pub intensity_curve: EasingCurve<f32>,
+    /// The curve the light's radius is multiplied by over its lifetime.
+    pub radius_curve: EasingCurve<f32>,
+}
+
+impl Default for TransientLight2d {
+    /// Returns a white light that fades out over a tenth of a second.
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            radius: 0.5,
+            falloff: 0.0,
+            duration: 0.1,
+            elapsed: 0.0,
+            intensity_curve: EasingCurve::new(1.0, 0.0, EaseFunction::QuadraticOut),
+            radius_curve: EasingCurve::new(1.0, 1.0, EaseFunction::Linear),
+        }
+    }
+}
+
+impl TransientLight2d {
+    /// Returns how far through its lifetime the light is, from `0.0` to `1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        (self.elapsed / self.duration).clamp(0.0, 1.0)
+    }
+
+    /// Returns the light's intensity at its current point in time.
+    pub fn current_intensity(&self) -> f32 {
+        self.intensity * self.intensity_curve.sample_clamped(self.progress())
+    }
+
+    /// Returns the light's radius at its current point in time.
+    pub fn current_radius(&self) -> f32 {
+        self.radius * self.radius_curve.sample_clamped(self.progress())
+    }
+}
+
+/// An extension trait for spawning transient lights with [`Commands`].
+pub trait LightFlashCommandsExt {
+    /// Spawns a [`TransientLight2d`] at the given position.
+    fn spawn_light_flash(&mut self, position: Vec2, light: TransientLight2d) -> EntityCommands<'_>;
+}
+
+impl LightFlashCommandsExt for Commands<'_, '_> {
+    fn spawn_light_flash(&mut self, position: Vec2, light: TransientLight2d) -> EntityCommands<'_> {
+        self.spawn((light, Transform::from_translation(position.extend(0.0))))
+    }
+}
+
+pub(crate) fn tick_transient_lights(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut lights: Query<(Entity, &mut TransientLight2d)>,
+) {
+    for (entity, mut light) in &mut lights {
+        light.elapsed += time.delta_secs();
+
+        if light.elapsed >= light.duration {
+            commands.entity(entity).despawn();
+        }
+    }
+}